@@ -0,0 +1,34 @@
+use crate::api::types::CurveDefinitionDto;
+use crate::conversions::name_to_response_curve;
+use crate::curves::{OutputRange, UnifiedCurve};
+use crate::profile::profiles::CurveParams;
+
+/// Upper bound on preview resolution to keep IPC payloads small.
+pub const MAX_CURVE_SAMPLES: usize = 1024;
+
+/// Sample a curve definition through the same `UnifiedCurve` used by the mapping engine.
+/// Returns `(input, output)` pairs evenly spaced over the raw input range.
+pub fn sample_curve(definition: &CurveDefinitionDto, sample_count: usize) -> Vec<(f32, f32)> {
+    let curve = UnifiedCurve::new(
+        name_to_response_curve(&definition.response_curve),
+        CurveParams {
+            use_smooth_interpolation: definition.use_smooth_curve,
+            custom_points: definition.custom_points.clone(),
+            strength: definition.curve_strength,
+            bezier_handles: definition.bezier_handles,
+        },
+        definition.dead_zone_inner,
+        definition.dead_zone_outer,
+    );
+    let output = OutputRange::new(
+        definition.output_min,
+        definition.output_max,
+        definition.output_gain,
+    );
+
+    curve
+        .sample(sample_count.min(MAX_CURVE_SAMPLES))
+        .into_iter()
+        .map(|(x, y)| (x, output.apply(y)))
+        .collect()
+}
@@ -1,3 +1,4 @@
+pub mod curves;
 pub mod logging;
 pub mod mappings;
 pub mod profiles;
@@ -5,6 +6,7 @@ pub mod system;
 pub mod types;
 // conversions.rs moved to root - now using crate::conversions
 
+pub use curves::*;
 pub use logging::*;
 pub use mappings::*;
 pub use profiles::*;
@@ -1,14 +1,28 @@
-use crate::api::types::{MappingDto, ProfileMetadataDto, SubProfileMetadataDto};
+use crate::api::types::{
+    ChordDto, CurvePresetDto, MacroDto, MacroStepDto, MappingDto, ProfileMetadataDto,
+    SubProfileMetadataDto, SubProfileSettingsDto,
+};
 use crate::conversions::{
-    gamepad_control_to_name, get_all_gamepad_control_names, get_all_supported_key_names,
-    name_to_gamepad_control, name_to_response_curve, response_curve_to_name,
+    button_behavior_to_name, combine_mode_to_name, gamepad_control_to_name,
+    get_all_gamepad_control_names, get_all_supported_key_names, hotkey_mode_to_name,
+    hotkey_to_metadata_string, key_name_to_vk, metadata_hotkey_to_struct, name_to_button_behavior,
+    name_to_combine_mode, name_to_gamepad_control, name_to_hotkey_mode, name_to_output_axis,
+    name_to_response_curve, name_to_smoothing_filter, name_to_socd_policy, name_to_stick_axis,
+    name_to_stick_shape, name_to_stick_side, output_axis_to_name, response_curve_to_name,
+    smoothing_filter_to_name, socd_policy_to_name, stick_axis_to_name, stick_shape_to_name,
+    stick_side_to_name,
 };
 use crate::input::{remove_hotkeys_for_profile, sync_hotkeys_for_profile};
-use crate::profile::profiles::{CurveParams, KeyMapping};
+use crate::profile::profiles::{
+    ChordMapping, CurveParams, CurvePreset, GamepadMacro, KeyMapping, MacroStep, SteeringSettings,
+    StickModifier, SubProfileSettings,
+};
 use crate::profile::{
-    update_systems_after_profile_switch, ProfileManager, SubProfileDeletionOutcome,
+    update_systems_after_profile_switch, MappingValidation, ProfileManager,
+    SubProfileDeletionOutcome,
 };
 use crate::PROFILE_MANAGER;
+use std::collections::HashMap;
 use std::sync::MutexGuard;
 use uuid::Uuid;
 
@@ -67,6 +81,7 @@ pub fn get_sub_profile_metadata(
             name: meta.name.clone(),
             description: meta.description.clone(),
             hotkey: meta.hotkey.clone(),
+            hotkey_mode: hotkey_mode_to_name(&meta.hotkey_mode).to_string(),
             created_at: meta.created_at,
             modified_at: meta.modified_at,
         })
@@ -121,16 +136,40 @@ pub fn get_current_mapping_info(index: usize) -> Option<MappingDto> {
             response_curve,
             dead_zone_inner: mapping.dead_zone_inner,
             dead_zone_outer: mapping.dead_zone_outer,
+            dead_zone_hysteresis: mapping.dead_zone_hysteresis,
             use_smooth_curve: mapping.curve_params.use_smooth_interpolation,
             custom_point_count,
             custom_points,
+            curve_strength: mapping.curve_params.strength,
+            bezier_handles: mapping.curve_params.bezier_handles,
+            curve_preset_id: mapping.curve_preset_id.map(|id| id.to_bytes_le()),
+            output_min: mapping.output_min,
+            output_max: mapping.output_max,
+            output_gain: mapping.output_gain,
+            analog_actuation: mapping.analog_actuation,
+            actuation_depth: mapping.actuation_depth,
+            release_depth: mapping.release_depth,
+            rapid_trigger: mapping.rapid_trigger,
+            rapid_trigger_sensitivity: mapping.rapid_trigger_sensitivity,
+            button_behavior: button_behavior_to_name(&mapping.button_behavior).to_string(),
+            turbo_rate: mapping.turbo_rate,
+            stick_angle: mapping.stick_angle,
+            smoothing: smoothing_filter_to_name(&mapping.smoothing).to_string(),
+            smoothing_cutoff: mapping.smoothing_cutoff,
+            smoothing_beta: mapping.smoothing_beta,
+            invert_output: mapping.invert_output,
+            invert_direction: mapping.invert_direction,
+            acceleration: mapping.acceleration,
+            acceleration_onset_ms: mapping.acceleration_onset_ms,
+            acceleration_ramp_ms: mapping.acceleration_ramp_ms,
+            acceleration_max_boost: mapping.acceleration_max_boost,
             created_at: mapping.created_at,
         }
     })
 }
 
-/// Update or insert a mapping in the active sub-profile.
-pub fn set_mapping(mapping: MappingDto) -> Result<(), String> {
+/// Convert a UI mapping into the profile model.
+fn key_mapping_from_dto(mapping: &MappingDto) -> Result<KeyMapping, String> {
     let gamepad_control = name_to_gamepad_control(&mapping.gamepad_control)
         .ok_or_else(|| format!("Invalid gamepad control: {}", mapping.gamepad_control))?;
     let response_curve = name_to_response_curve(&mapping.response_curve);
@@ -157,19 +196,51 @@ pub fn set_mapping(mapping: MappingDto) -> Result<(), String> {
         response_curve,
         dead_zone_inner: mapping.dead_zone_inner,
         dead_zone_outer: mapping.dead_zone_outer,
+        dead_zone_hysteresis: mapping.dead_zone_hysteresis,
         curve_params: CurveParams {
             use_smooth_interpolation: mapping.use_smooth_curve,
             custom_points,
+            strength: mapping.curve_strength,
+            bezier_handles: mapping.bezier_handles,
         },
+        curve_preset_id: mapping.curve_preset_id.map(Uuid::from_bytes_le),
+        output_min: mapping.output_min,
+        output_max: mapping.output_max,
+        output_gain: mapping.output_gain,
+        analog_actuation: mapping.analog_actuation,
+        actuation_depth: mapping.actuation_depth,
+        release_depth: mapping.release_depth,
+        rapid_trigger: mapping.rapid_trigger,
+        rapid_trigger_sensitivity: mapping.rapid_trigger_sensitivity,
+        button_behavior: name_to_button_behavior(&mapping.button_behavior),
+        turbo_rate: mapping.turbo_rate,
+        stick_angle: mapping.stick_angle,
+        smoothing: name_to_smoothing_filter(&mapping.smoothing),
+        smoothing_cutoff: mapping.smoothing_cutoff,
+        smoothing_beta: mapping.smoothing_beta,
+        invert_output: mapping.invert_output,
+        invert_direction: mapping.invert_direction,
+        acceleration: mapping.acceleration,
+        acceleration_onset_ms: mapping.acceleration_onset_ms,
+        acceleration_ramp_ms: mapping.acceleration_ramp_ms,
+        acceleration_max_boost: mapping.acceleration_max_boost,
         created_at,
         modified_at: now,
     };
 
+    Ok(key_mapping)
+}
+
+/// Update or insert a mapping (one key/control output) in the active sub-profile.
+/// `replace_other_outputs` drops the key's mappings to other controls.
+pub fn set_mapping(mapping: MappingDto, replace_other_outputs: bool) -> Result<(), String> {
+    let key_mapping = key_mapping_from_dto(&mapping)?;
+
     {
         let mut guard = lock_manager()?;
         let manager = guard.as_mut().ok_or_else(manager_unavailable)?;
         manager
-            .set_current_mapping(key_mapping)
+            .set_current_mapping(key_mapping, replace_other_outputs)
             .map_err(|e| e.to_string())?;
     }
 
@@ -177,13 +248,52 @@ pub fn set_mapping(mapping: MappingDto) -> Result<(), String> {
     Ok(())
 }
 
-/// Remove a mapping by key name from the active sub-profile.
-pub fn remove_mapping(key_name: &str) -> Result<bool, String> {
+/// Validate a mapping without storing it, with its curve preset's curve when referenced.
+pub fn validate_mapping(mapping: &MappingDto) -> Result<MappingValidation, String> {
+    let key_mapping = key_mapping_from_dto(mapping)?;
+    let guard = lock_manager()?;
+    Ok(match guard.as_ref() {
+        Some(manager) => manager.validate_resolved_mapping(&key_mapping),
+        None => crate::profile::validate_mapping(&key_mapping),
+    })
+}
+
+/// Validate every mapping in the active sub-profile.
+pub fn validate_current_mappings() -> Vec<MappingValidation> {
+    PROFILE_MANAGER
+        .lock()
+        .ok()
+        .and_then(|guard| {
+            guard
+                .as_ref()
+                .map(|manager| manager.validate_current_mappings())
+        })
+        .unwrap_or_default()
+}
+
+/// Enable or disable rejection of invalid mappings in `set_mapping`.
+pub fn set_strict_validation(enabled: bool) -> Result<(), String> {
+    let mut guard = lock_manager()?;
+    let manager = guard.as_mut().ok_or_else(manager_unavailable)?;
+    manager.set_strict_validation(enabled);
+    Ok(())
+}
+
+/// Remove a key's mappings from the active sub-profile.
+/// With a gamepad control only that output is removed.
+pub fn remove_mapping(key_name: &str, gamepad_control: Option<&str>) -> Result<bool, String> {
+    let gamepad_control = gamepad_control
+        .map(|name| {
+            name_to_gamepad_control(name)
+                .ok_or_else(|| format!("Invalid gamepad control: {}", name))
+        })
+        .transpose()?;
+
     let removed = {
         let mut guard = lock_manager()?;
         let manager = guard.as_mut().ok_or_else(manager_unavailable)?;
         manager
-            .remove_current_mapping(key_name)
+            .remove_current_mapping(key_name, gamepad_control)
             .map_err(|e| e.to_string())?
     };
 
@@ -194,6 +304,428 @@ pub fn remove_mapping(key_name: &str) -> Result<bool, String> {
     Ok(removed)
 }
 
+/// Processing settings of the active sub-profile.
+pub fn get_current_sub_profile_settings() -> Option<SubProfileSettingsDto> {
+    let guard = PROFILE_MANAGER.lock().ok()?;
+    let manager = guard.as_ref()?;
+    let settings = manager.get_current_sub_profile_settings()?;
+
+    let mut combine_modes: Vec<_> = settings.combine_modes.iter().collect();
+    combine_modes.sort_by_key(|(control, _)| control.analog_index());
+
+    let mut steering: Vec<_> = settings.steering.iter().collect();
+    steering.sort_by_key(|(axis, _)| axis.index());
+
+    let mut slew_rates: Vec<_> = settings.slew_rates.iter().collect();
+    slew_rates.sort_by_key(|(axis, _)| axis.index());
+
+    Some(SubProfileSettingsDto {
+        stick_shape: stick_shape_to_name(&settings.stick_shape).to_string(),
+        radial_dead_zone: settings.radial_dead_zone,
+        socd_policy: socd_policy_to_name(&settings.socd_policy).to_string(),
+        combine_modes: combine_modes
+            .into_iter()
+            .map(|(control, mode)| {
+                (
+                    gamepad_control_to_name(control).to_string(),
+                    combine_mode_to_name(mode).to_string(),
+                )
+            })
+            .collect(),
+        stick_modifiers: settings
+            .stick_modifiers
+            .iter()
+            .map(|modifier| {
+                (
+                    modifier.key_name.clone(),
+                    stick_side_to_name(&modifier.stick).to_string(),
+                    modifier.scale,
+                )
+            })
+            .collect(),
+        steering: steering
+            .into_iter()
+            .map(|(axis, rates)| {
+                (
+                    stick_axis_to_name(axis).to_string(),
+                    rates.steer_rate,
+                    rates.return_rate,
+                )
+            })
+            .collect(),
+        slew_rates: slew_rates
+            .into_iter()
+            .map(|(axis, rate)| (output_axis_to_name(axis).to_string(), *rate))
+            .collect(),
+        swap_sticks: settings.swap_sticks,
+        invert_left_y: settings.invert_left_y,
+        invert_right_y: settings.invert_right_y,
+    })
+}
+
+/// Update the processing settings of the active sub-profile.
+pub fn set_current_sub_profile_settings(settings: SubProfileSettingsDto) -> Result<(), String> {
+    let mut combine_modes = HashMap::new();
+    for (control_name, mode_name) in &settings.combine_modes {
+        let control = name_to_gamepad_control(control_name)
+            .ok_or_else(|| format!("Invalid gamepad control: {}", control_name))?;
+        if control.analog_index().is_none() {
+            return Err(format!(
+                "Combine modes only apply to analog controls: {}",
+                control_name
+            ));
+        }
+        combine_modes.insert(control, name_to_combine_mode(mode_name));
+    }
+
+    let mut stick_modifiers = Vec::new();
+    for (key_name, side_name, scale) in &settings.stick_modifiers {
+        if key_name_to_vk(key_name) == 0 {
+            return Err(format!("Invalid stick modifier key: {}", key_name));
+        }
+        if !scale.is_finite() || !(0.0..=2.0).contains(scale) {
+            return Err(format!(
+                "Stick modifier scale must be between 0.0 and 2.0: {}",
+                scale
+            ));
+        }
+        stick_modifiers.push(StickModifier {
+            key_name: key_name.clone(),
+            stick: name_to_stick_side(side_name),
+            scale: *scale,
+        });
+    }
+
+    let mut steering = HashMap::new();
+    for (axis_name, steer_rate, return_rate) in &settings.steering {
+        let axis = name_to_stick_axis(axis_name)
+            .ok_or_else(|| format!("Invalid stick axis: {}", axis_name))?;
+        if !steer_rate.is_finite() || *steer_rate <= 0.0 {
+            return Err(format!("Steering rate must be positive: {}", steer_rate));
+        }
+        if !return_rate.is_finite() || *return_rate < 0.0 {
+            return Err(format!(
+                "Steering return rate must not be negative: {}",
+                return_rate
+            ));
+        }
+        steering.insert(
+            axis,
+            SteeringSettings {
+                steer_rate: *steer_rate,
+                return_rate: *return_rate,
+            },
+        );
+    }
+
+    let mut slew_rates = HashMap::new();
+    for (axis_name, rate) in &settings.slew_rates {
+        let axis = name_to_output_axis(axis_name)
+            .ok_or_else(|| format!("Invalid output axis: {}", axis_name))?;
+        if !rate.is_finite() || *rate <= 0.0 {
+            return Err(format!("Slew rate must be positive: {}", rate));
+        }
+        slew_rates.insert(axis, *rate);
+    }
+
+    let sub_profile_settings = SubProfileSettings {
+        stick_shape: name_to_stick_shape(&settings.stick_shape),
+        radial_dead_zone: settings.radial_dead_zone,
+        socd_policy: name_to_socd_policy(&settings.socd_policy),
+        combine_modes,
+        stick_modifiers,
+        steering,
+        slew_rates,
+        swap_sticks: settings.swap_sticks,
+        invert_left_y: settings.invert_left_y,
+        invert_right_y: settings.invert_right_y,
+    };
+
+    {
+        let mut guard = lock_manager()?;
+        let manager = guard.as_mut().ok_or_else(manager_unavailable)?;
+        manager
+            .set_current_sub_profile_settings(sub_profile_settings)
+            .map_err(|e| e.to_string())?;
+    }
+
+    update_systems_after_profile_switch();
+    Ok(())
+}
+
+/// List the curve presets of the loaded profile.
+pub fn get_curve_presets() -> Vec<CurvePresetDto> {
+    let Ok(guard) = PROFILE_MANAGER.lock() else {
+        return Vec::new();
+    };
+    let Some(manager) = guard.as_ref() else {
+        return Vec::new();
+    };
+
+    manager
+        .get_curve_presets()
+        .iter()
+        .map(|preset| CurvePresetDto {
+            id: preset.id.to_bytes_le(),
+            name: preset.name.clone(),
+            response_curve: response_curve_to_name(&preset.response_curve).to_string(),
+            use_smooth_curve: preset.curve_params.use_smooth_interpolation,
+            custom_points: preset
+                .curve_params
+                .custom_points
+                .iter()
+                .cloned()
+                .take(16)
+                .collect(),
+            curve_strength: preset.curve_params.strength,
+            bezier_handles: preset.curve_params.bezier_handles,
+            created_at: preset.created_at,
+            modified_at: preset.modified_at,
+        })
+        .collect()
+}
+
+/// Create or update a curve preset and recompile the sub-profiles using it.
+/// A nil id creates a new preset. Returns the preset id.
+pub fn set_curve_preset(preset: CurvePresetDto) -> Result<Uuid, String> {
+    let now = crate::profile::profiles::now_timestamp();
+    let curve_preset = CurvePreset {
+        id: Uuid::from_bytes_le(preset.id),
+        name: preset.name,
+        response_curve: name_to_response_curve(&preset.response_curve),
+        curve_params: CurveParams {
+            use_smooth_interpolation: preset.use_smooth_curve,
+            custom_points: preset.custom_points.into_iter().take(16).collect(),
+            strength: preset.curve_strength,
+            bezier_handles: preset.bezier_handles,
+        },
+        created_at: now,
+        modified_at: now,
+    };
+
+    let preset_id = {
+        let mut guard = lock_manager()?;
+        let manager = guard.as_mut().ok_or_else(manager_unavailable)?;
+        manager
+            .set_curve_preset(curve_preset)
+            .map_err(|e| e.to_string())?
+    };
+
+    update_systems_after_profile_switch();
+    Ok(preset_id)
+}
+
+/// Delete a curve preset; mappings using it keep a copy of its curve.
+pub fn delete_curve_preset(preset_id: &Uuid) -> Result<(), String> {
+    let mut guard = lock_manager()?;
+    let manager = guard.as_mut().ok_or_else(manager_unavailable)?;
+    manager
+        .delete_curve_preset(preset_id)
+        .map_err(|e| e.to_string())
+}
+
+fn macro_step_to_dto(step: &MacroStep) -> MacroStepDto {
+    let (action, control, value, duration_ms) = match step {
+        MacroStep::Press { control } => ("Press", Some(control), 1.0, 0),
+        MacroStep::Release { control } => ("Release", Some(control), 0.0, 0),
+        MacroStep::Set { control, value } => ("Set", Some(control), *value, 0),
+        MacroStep::Wait { duration_ms } => ("Wait", None, 0.0, *duration_ms),
+    };
+    MacroStepDto {
+        action: action.to_string(),
+        control: control
+            .map(|control| gamepad_control_to_name(control).to_string())
+            .unwrap_or_default(),
+        value,
+        duration_ms,
+    }
+}
+
+fn macro_step_from_dto(step: &MacroStepDto) -> Result<MacroStep, String> {
+    if step.action == "Wait" {
+        return Ok(MacroStep::Wait {
+            duration_ms: step.duration_ms,
+        });
+    }
+
+    let control = name_to_gamepad_control(&step.control)
+        .filter(|control| !control.is_stick_angle())
+        .ok_or_else(|| format!("Invalid gamepad control: {}", step.control))?;
+    match step.action.as_str() {
+        "Press" => Ok(MacroStep::Press { control }),
+        "Release" => Ok(MacroStep::Release { control }),
+        "Set" if !(0.0..=1.0).contains(&step.value) => {
+            Err(format!("Invalid macro value: {}", step.value))
+        }
+        "Set" => Ok(MacroStep::Set {
+            control,
+            value: step.value,
+        }),
+        other => Err(format!("Invalid macro action: {}", other)),
+    }
+}
+
+/// List the macros of the active sub-profile.
+pub fn get_macros() -> Vec<MacroDto> {
+    let Ok(guard) = PROFILE_MANAGER.lock() else {
+        return Vec::new();
+    };
+    let Some(manager) = guard.as_ref() else {
+        return Vec::new();
+    };
+
+    manager
+        .get_current_macros()
+        .iter()
+        .map(|gamepad_macro| MacroDto {
+            id: gamepad_macro.id.to_bytes_le(),
+            name: gamepad_macro.name.clone(),
+            trigger: hotkey_to_metadata_string(&gamepad_macro.trigger),
+            steps: gamepad_macro.steps.iter().map(macro_step_to_dto).collect(),
+            created_at: gamepad_macro.created_at,
+        })
+        .collect()
+}
+
+/// Create or update a macro of the active sub-profile.
+/// A nil id creates a new macro. Returns the macro id.
+pub fn set_macro(gamepad_macro: MacroDto) -> Result<Uuid, String> {
+    let trigger = metadata_hotkey_to_struct(&gamepad_macro.trigger)
+        .ok_or_else(|| format!("Invalid macro trigger: {}", gamepad_macro.trigger))?;
+    let steps = gamepad_macro
+        .steps
+        .iter()
+        .map(macro_step_from_dto)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let now = crate::profile::profiles::now_timestamp();
+    let gamepad_macro = GamepadMacro {
+        id: Uuid::from_bytes_le(gamepad_macro.id),
+        name: gamepad_macro.name,
+        trigger,
+        steps,
+        created_at: now,
+        modified_at: now,
+    };
+
+    let macro_id = {
+        let mut guard = lock_manager()?;
+        let manager = guard.as_mut().ok_or_else(manager_unavailable)?;
+        manager
+            .set_current_macro(gamepad_macro)
+            .map_err(|e| e.to_string())?
+    };
+
+    update_systems_after_profile_switch();
+    Ok(macro_id)
+}
+
+/// Delete a macro of the active sub-profile.
+pub fn delete_macro(macro_id: &Uuid) -> Result<(), String> {
+    {
+        let mut guard = lock_manager()?;
+        let manager = guard.as_mut().ok_or_else(manager_unavailable)?;
+        manager
+            .delete_current_macro(macro_id)
+            .map_err(|e| e.to_string())?;
+    }
+
+    update_systems_after_profile_switch();
+    Ok(())
+}
+
+/// List the chords of the active sub-profile.
+pub fn get_chords() -> Vec<ChordDto> {
+    let Ok(guard) = PROFILE_MANAGER.lock() else {
+        return Vec::new();
+    };
+    let Some(manager) = guard.as_ref() else {
+        return Vec::new();
+    };
+
+    manager
+        .get_current_chords()
+        .iter()
+        .map(|chord| ChordDto {
+            id: chord.id.to_bytes_le(),
+            name: chord.name.clone(),
+            keys: chord.keys.clone(),
+            outputs: chord
+                .outputs
+                .iter()
+                .map(|control| gamepad_control_to_name(control).to_string())
+                .collect(),
+            suppress_keys: chord.suppress_keys,
+            created_at: chord.created_at,
+        })
+        .collect()
+}
+
+/// Create or update a chord of the active sub-profile.
+/// A nil id creates a new chord. Returns the chord id.
+pub fn set_chord(chord: ChordDto) -> Result<Uuid, String> {
+    for key_name in &chord.keys {
+        if key_name_to_vk(key_name) == 0 {
+            return Err(format!("Invalid chord key: {}", key_name));
+        }
+    }
+    let mut key_codes: Vec<u16> = chord.keys.iter().map(|key| key_name_to_vk(key)).collect();
+    key_codes.sort_unstable();
+    key_codes.dedup();
+    if key_codes.len() < 2 {
+        return Err("A chord needs at least two different keys".to_string());
+    }
+
+    let outputs = chord
+        .outputs
+        .iter()
+        .map(|name| {
+            name_to_gamepad_control(name)
+                .filter(|control| !control.is_stick_angle())
+                .ok_or_else(|| format!("Invalid chord output: {}", name))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if outputs.is_empty() {
+        return Err("A chord needs at least one output".to_string());
+    }
+
+    let now = crate::profile::profiles::now_timestamp();
+    let chord = ChordMapping {
+        id: Uuid::from_bytes_le(chord.id),
+        name: chord.name,
+        keys: chord.keys,
+        outputs,
+        suppress_keys: chord.suppress_keys,
+        created_at: now,
+        modified_at: now,
+    };
+
+    let chord_id = {
+        let mut guard = lock_manager()?;
+        let manager = guard.as_mut().ok_or_else(manager_unavailable)?;
+        manager
+            .set_current_chord(chord)
+            .map_err(|e| e.to_string())?
+    };
+
+    update_systems_after_profile_switch();
+    Ok(chord_id)
+}
+
+/// Delete a chord of the active sub-profile.
+pub fn delete_chord(chord_id: &Uuid) -> Result<(), String> {
+    {
+        let mut guard = lock_manager()?;
+        let manager = guard.as_mut().ok_or_else(manager_unavailable)?;
+        manager
+            .delete_current_chord(chord_id)
+            .map_err(|e| e.to_string())?;
+    }
+
+    update_systems_after_profile_switch();
+    Ok(())
+}
+
 /// Permanently delete a profile by UUID.
 pub fn delete_profile(profile_id: &Uuid) -> Result<(), String> {
     {
@@ -336,17 +868,24 @@ pub fn update_profile_hotkey(profile_id: &Uuid, hotkey: &str) -> Result<(), Stri
     Ok(())
 }
 
-/// Update a sub-profile hotkey.
+/// Update a sub-profile hotkey. `hotkey_mode` ("Switch" or "Hold") keeps the
+/// current mode when `None`.
 pub fn update_sub_profile_hotkey(
     profile_id: &Uuid,
     sub_profile_id: &Uuid,
     hotkey: &str,
+    hotkey_mode: Option<&str>,
 ) -> Result<(), String> {
     {
         let mut guard = lock_manager()?;
         let manager = guard.as_mut().ok_or_else(manager_unavailable)?;
         manager
-            .set_sub_profile_hotkey(profile_id, sub_profile_id, optional_slice(hotkey))
+            .set_sub_profile_hotkey(
+                profile_id,
+                sub_profile_id,
+                optional_slice(hotkey),
+                hotkey_mode.map(name_to_hotkey_mode),
+            )
             .map_err(|e| e.to_string())?;
     }
 
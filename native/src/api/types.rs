@@ -106,10 +106,26 @@ pub struct SubProfileMetadataDto {
     pub name: String,
     pub description: String,
     pub hotkey: Option<String>,
+    pub hotkey_mode: String,
     pub created_at: u64,
     pub modified_at: u64,
 }
 
+/// UI-facing curve definition used for previews.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurveDefinitionDto {
+    pub response_curve: String,
+    pub dead_zone_inner: f32,
+    pub dead_zone_outer: f32,
+    pub use_smooth_curve: bool,
+    pub custom_points: Vec<(f32, f32)>,
+    pub curve_strength: f32,
+    pub bezier_handles: [(f32, f32); 2],
+    pub output_min: f32,
+    pub output_max: f32,
+    pub output_gain: f32,
+}
+
 /// UI-facing mapping information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MappingDto {
@@ -118,8 +134,91 @@ pub struct MappingDto {
     pub response_curve: String,
     pub dead_zone_inner: f32,
     pub dead_zone_outer: f32,
+    pub dead_zone_hysteresis: f32,
     pub use_smooth_curve: bool,
     pub custom_point_count: u32,
     pub custom_points: Vec<(f32, f32)>,
+    pub curve_strength: f32,
+    pub bezier_handles: [(f32, f32); 2],
+    pub curve_preset_id: Option<[u8; 16]>,
+    pub output_min: f32,
+    pub output_max: f32,
+    pub output_gain: f32,
+    pub analog_actuation: bool,
+    pub actuation_depth: f32,
+    pub release_depth: f32,
+    pub rapid_trigger: bool,
+    pub rapid_trigger_sensitivity: f32,
+    pub button_behavior: String,
+    pub turbo_rate: f32,
+    pub stick_angle: f32,
+    pub smoothing: String,
+    pub smoothing_cutoff: f32,
+    pub smoothing_beta: f32,
+    pub invert_output: bool,
+    pub invert_direction: bool,
+    pub acceleration: bool,
+    pub acceleration_onset_ms: u32,
+    pub acceleration_ramp_ms: u32,
+    pub acceleration_max_boost: f32,
+    pub created_at: u64,
+}
+
+/// UI-facing processing settings of a sub-profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubProfileSettingsDto {
+    pub stick_shape: String,
+    pub radial_dead_zone: f32,
+    pub socd_policy: String,
+    pub combine_modes: Vec<(String, String)>, // (gamepad control, combine mode)
+    pub stick_modifiers: Vec<(String, String, f32)>, // (key name, stick side, scale)
+    pub steering: Vec<(String, f32, f32)>,    // (stick axis, steer rate, return rate)
+    pub slew_rates: Vec<(String, f32)>,       // (output axis, maximum change per second)
+    pub swap_sticks: bool,
+    pub invert_left_y: bool,
+    pub invert_right_y: bool,
+}
+
+/// UI-facing gamepad macro of a sub-profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MacroDto {
+    pub id: [u8; 16],
+    pub name: String,
+    pub trigger: String, // Hotkey string, e.g. "Ctrl+F1"
+    pub steps: Vec<MacroStepDto>,
+    pub created_at: u64,
+}
+
+/// UI-facing chord mapping of a sub-profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChordDto {
+    pub id: [u8; 16],
+    pub name: String,
+    pub keys: Vec<String>,    // Key names, e.g. ["Q", "E"]
+    pub outputs: Vec<String>, // Gamepad control names
+    pub suppress_keys: bool,
+    pub created_at: u64,
+}
+
+/// UI-facing macro step. Fields unused by the action are ignored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MacroStepDto {
+    pub action: String, // "Press", "Release", "Set" or "Wait"
+    pub control: String,
+    pub value: f32,
+    pub duration_ms: u32,
+}
+
+/// UI-facing curve preset shared by mappings of a profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurvePresetDto {
+    pub id: [u8; 16],
+    pub name: String,
+    pub response_curve: String,
+    pub use_smooth_curve: bool,
+    pub custom_points: Vec<(f32, f32)>,
+    pub curve_strength: f32,
+    pub bezier_handles: [(f32, f32); 2],
     pub created_at: u64,
+    pub modified_at: u64,
 }
@@ -4,10 +4,13 @@ use log::{info, warn};
 use std::path::PathBuf;
 use std::sync::OnceLock;
 use universal_analog_input::api;
-use universal_analog_input::api::types::MappingDto;
+use universal_analog_input::api::types::{
+    ChordDto, CurveDefinitionDto, CurvePresetDto, MacroDto, MappingDto, SubProfileSettingsDto,
+};
 use universal_analog_input::ipc::protocol::{IpcCommandType, IpcResponseType};
 use universal_analog_input::ipc::{
-    IpcCommand, IpcResponse, MappingInfo, ProfileMetadata, SubProfileMetadata,
+    ChordInfo, CurvePresetInfo, IpcCommand, IpcResponse, MacroInfo, MappingInfo, ProfileMetadata,
+    SubProfileMetadata, SubProfileSettingsInfo,
 };
 use uuid::Uuid;
 
@@ -130,11 +133,161 @@ impl CommandHandler {
                 }
             }
 
+            IpcCommandType::SampleCurve {
+                curve,
+                sample_count,
+            } => {
+                let samples =
+                    api::sample_curve(&CurveDefinitionDto::from(curve), sample_count as usize);
+                IpcResponse::response(message_id, IpcResponseType::CurveSamples { samples })
+            }
+
+            IpcCommandType::ValidateMapping { mapping } => {
+                match api::validate_mapping(&MappingDto::from(mapping)) {
+                    Ok(validation) => IpcResponse::response(
+                        message_id,
+                        IpcResponseType::MappingValidation { data: validation },
+                    ),
+                    Err(e) => {
+                        IpcResponse::response(message_id, IpcResponseType::Error { message: e })
+                    }
+                }
+            }
+
+            IpcCommandType::ValidateCurrentMappings => IpcResponse::response(
+                message_id,
+                IpcResponseType::MappingValidations {
+                    data: api::validate_current_mappings(),
+                },
+            ),
+
+            IpcCommandType::SetStrictValidation { enabled } => {
+                match api::set_strict_validation(enabled) {
+                    Ok(_) => IpcResponse::response(message_id, IpcResponseType::Success),
+                    Err(e) => {
+                        IpcResponse::response(message_id, IpcResponseType::Error { message: e })
+                    }
+                }
+            }
+
+            IpcCommandType::GetSubProfileSettings => {
+                match api::get_current_sub_profile_settings() {
+                    Some(settings) => IpcResponse::response(
+                        message_id,
+                        IpcResponseType::SubProfileSettings {
+                            data: SubProfileSettingsInfo::from(settings),
+                        },
+                    ),
+                    None => IpcResponse::response(
+                        message_id,
+                        IpcResponseType::Error {
+                            message: "No active sub-profile".to_string(),
+                        },
+                    ),
+                }
+            }
+
+            IpcCommandType::SetSubProfileSettings { settings } => {
+                match api::set_current_sub_profile_settings(SubProfileSettingsDto::from(settings)) {
+                    Ok(_) => IpcResponse::response(message_id, IpcResponseType::Success),
+                    Err(e) => {
+                        IpcResponse::response(message_id, IpcResponseType::Error { message: e })
+                    }
+                }
+            }
+
+            IpcCommandType::GetCurvePresets => {
+                let presets = api::get_curve_presets()
+                    .into_iter()
+                    .map(CurvePresetInfo::from)
+                    .collect();
+                IpcResponse::response(message_id, IpcResponseType::CurvePresets { data: presets })
+            }
+
+            IpcCommandType::SetCurvePreset { preset } => {
+                match api::set_curve_preset(CurvePresetDto::from(preset)) {
+                    Ok(id) => IpcResponse::response(
+                        message_id,
+                        IpcResponseType::CurvePresetSaved {
+                            id: id.to_bytes_le(),
+                        },
+                    ),
+                    Err(e) => {
+                        IpcResponse::response(message_id, IpcResponseType::Error { message: e })
+                    }
+                }
+            }
+
+            IpcCommandType::DeleteCurvePreset { preset_id } => {
+                let preset_id = bytes_to_uuid(&preset_id);
+                match api::delete_curve_preset(&preset_id) {
+                    Ok(_) => IpcResponse::response(message_id, IpcResponseType::Success),
+                    Err(e) => {
+                        IpcResponse::response(message_id, IpcResponseType::Error { message: e })
+                    }
+                }
+            }
+
+            IpcCommandType::GetMacros => {
+                let macros = api::get_macros().into_iter().map(MacroInfo::from).collect();
+                IpcResponse::response(message_id, IpcResponseType::Macros { data: macros })
+            }
+
+            IpcCommandType::SetMacro { macro_info } => {
+                match api::set_macro(MacroDto::from(macro_info)) {
+                    Ok(id) => IpcResponse::response(
+                        message_id,
+                        IpcResponseType::MacroSaved {
+                            id: id.to_bytes_le(),
+                        },
+                    ),
+                    Err(e) => {
+                        IpcResponse::response(message_id, IpcResponseType::Error { message: e })
+                    }
+                }
+            }
+
+            IpcCommandType::DeleteMacro { macro_id } => {
+                let macro_id = bytes_to_uuid(&macro_id);
+                match api::delete_macro(&macro_id) {
+                    Ok(_) => IpcResponse::response(message_id, IpcResponseType::Success),
+                    Err(e) => {
+                        IpcResponse::response(message_id, IpcResponseType::Error { message: e })
+                    }
+                }
+            }
+
+            IpcCommandType::GetChords => {
+                let chords = api::get_chords().into_iter().map(ChordInfo::from).collect();
+                IpcResponse::response(message_id, IpcResponseType::Chords { data: chords })
+            }
+
+            IpcCommandType::SetChord { chord } => match api::set_chord(ChordDto::from(chord)) {
+                Ok(id) => IpcResponse::response(
+                    message_id,
+                    IpcResponseType::ChordSaved {
+                        id: id.to_bytes_le(),
+                    },
+                ),
+                Err(e) => IpcResponse::response(message_id, IpcResponseType::Error { message: e }),
+            },
+
+            IpcCommandType::DeleteChord { chord_id } => {
+                let chord_id = bytes_to_uuid(&chord_id);
+                match api::delete_chord(&chord_id) {
+                    Ok(_) => IpcResponse::response(message_id, IpcResponseType::Success),
+                    Err(e) => {
+                        IpcResponse::response(message_id, IpcResponseType::Error { message: e })
+                    }
+                }
+            }
+
             IpcCommandType::SetMapping {
                 profile_id: _,
                 sub_profile_id: _,
                 mapping,
-            } => match api::set_mapping(MappingDto::from(mapping)) {
+                replace_other_outputs,
+            } => match api::set_mapping(MappingDto::from(mapping), replace_other_outputs) {
                 Ok(_) => IpcResponse::response(message_id, IpcResponseType::Success),
                 Err(e) => IpcResponse::response(message_id, IpcResponseType::Error { message: e }),
             },
@@ -143,7 +296,8 @@ impl CommandHandler {
                 profile_id: _,
                 sub_profile_id: _,
                 key_name,
-            } => match api::remove_mapping(&key_name) {
+                gamepad_control,
+            } => match api::remove_mapping(&key_name, gamepad_control.as_deref()) {
                 Ok(_) => IpcResponse::response(message_id, IpcResponseType::Success),
                 Err(e) => IpcResponse::response(message_id, IpcResponseType::Error { message: e }),
             },
@@ -255,10 +409,11 @@ impl CommandHandler {
                 profile_id,
                 sub_id,
                 hotkey,
+                hotkey_mode,
             } => {
                 let pid = bytes_to_uuid(&profile_id);
                 let sid = bytes_to_uuid(&sub_id);
-                match api::update_sub_profile_hotkey(&pid, &sid, &hotkey) {
+                match api::update_sub_profile_hotkey(&pid, &sid, &hotkey, hotkey_mode.as_deref()) {
                     Ok(_) => IpcResponse::response(message_id, IpcResponseType::Success),
                     Err(e) => {
                         IpcResponse::response(message_id, IpcResponseType::Error { message: e })
@@ -1,6 +1,10 @@
 //! Centralized conversion helpers for keys, gamepad controls, response curves, and hotkey metadata.
 
-use crate::profile::profiles::{GamepadControl, HotKey, ResponseCurve};
+use crate::mapping::socd::StickAxis;
+use crate::profile::profiles::{
+    ButtonBehavior, CombineMode, GamepadControl, HotKey, HotkeyMode, OutputAxis, ResponseCurve,
+    SmoothingFilter, SocdPolicy, StickShape, StickSide,
+};
 
 /// Windows Virtual Key constants used throughout the project.
 pub mod vk {
@@ -172,6 +176,17 @@ pub fn vk_to_key_name(vk_code: u16) -> &'static str {
     }
 }
 
+/// Left and right VK codes of a generic modifier key; other keys repeat their own code.
+pub fn vk_side_variants(vk_code: u16) -> [u16; 2] {
+    match vk_code {
+        vk::SHIFT => [vk::LSHIFT, vk::RSHIFT],
+        vk::CONTROL => [vk::LCONTROL, vk::RCONTROL],
+        vk::MENU => [vk::LMENU, vk::RMENU],
+        vk::LWIN => [vk::LWIN, vk::RWIN],
+        _ => [vk_code, vk_code],
+    }
+}
+
 /// Convert a display name to a VK code. Returns 0 when unknown.
 pub fn key_name_to_vk(key_name: &str) -> u16 {
     match key_name {
@@ -278,6 +293,13 @@ pub fn gamepad_control_to_name(control: &GamepadControl) -> &'static str {
         GamepadControl::DPadDown => "D-Pad Down",
         GamepadControl::DPadLeft => "D-Pad Left",
         GamepadControl::DPadRight => "D-Pad Right",
+        GamepadControl::Start => "Start",
+        GamepadControl::Back => "Back",
+        GamepadControl::Guide => "Guide",
+        GamepadControl::LeftThumb => "Left Stick Click",
+        GamepadControl::RightThumb => "Right Stick Click",
+        GamepadControl::LeftStickAngle => "Left Stick Angle",
+        GamepadControl::RightStickAngle => "Right Stick Angle",
     }
 }
 
@@ -304,6 +326,13 @@ pub fn name_to_gamepad_control(name: &str) -> Option<GamepadControl> {
         "D-Pad Down" => Some(GamepadControl::DPadDown),
         "D-Pad Left" => Some(GamepadControl::DPadLeft),
         "D-Pad Right" => Some(GamepadControl::DPadRight),
+        "Start" => Some(GamepadControl::Start),
+        "Back" => Some(GamepadControl::Back),
+        "Guide" => Some(GamepadControl::Guide),
+        "Left Stick Click" => Some(GamepadControl::LeftThumb),
+        "Right Stick Click" => Some(GamepadControl::RightThumb),
+        "Left Stick Angle" => Some(GamepadControl::LeftStickAngle),
+        "Right Stick Angle" => Some(GamepadControl::RightStickAngle),
         _ => None,
     }
 }
@@ -313,6 +342,11 @@ pub fn response_curve_to_name(curve: &ResponseCurve) -> &'static str {
     match curve {
         ResponseCurve::Linear => "Linear",
         ResponseCurve::Custom => "Custom",
+        ResponseCurve::Power => "Power",
+        ResponseCurve::Exponential => "Exponential",
+        ResponseCurve::SCurve => "S-Curve",
+        ResponseCurve::Logarithmic => "Logarithmic",
+        ResponseCurve::Bezier => "Bezier",
     }
 }
 
@@ -321,10 +355,191 @@ pub fn name_to_response_curve(name: &str) -> ResponseCurve {
     match name {
         "Linear" => ResponseCurve::Linear,
         "Custom" => ResponseCurve::Custom,
+        "Power" => ResponseCurve::Power,
+        "Exponential" => ResponseCurve::Exponential,
+        "S-Curve" => ResponseCurve::SCurve,
+        "Logarithmic" => ResponseCurve::Logarithmic,
+        "Bezier" => ResponseCurve::Bezier,
         _ => ResponseCurve::Linear, // Default fallback
     }
 }
 
+/// Convert a stick shape enum to its display name.
+pub fn stick_shape_to_name(shape: &StickShape) -> &'static str {
+    match shape {
+        StickShape::Square => "Square",
+        StickShape::CircularClamp => "Circular Clamp",
+        StickShape::SquareToCircle => "Square To Circle",
+    }
+}
+
+/// Convert a display name to a stick shape enum.
+pub fn name_to_stick_shape(name: &str) -> StickShape {
+    match name {
+        "Circular Clamp" => StickShape::CircularClamp,
+        "Square To Circle" => StickShape::SquareToCircle,
+        _ => StickShape::Square, // Default fallback
+    }
+}
+
+/// Convert a stick side enum to its display name.
+pub fn stick_side_to_name(side: &StickSide) -> &'static str {
+    match side {
+        StickSide::Left => "Left",
+        StickSide::Right => "Right",
+        StickSide::Both => "Both",
+    }
+}
+
+/// Convert a display name to a stick side enum.
+pub fn name_to_stick_side(name: &str) -> StickSide {
+    match name {
+        "Right" => StickSide::Right,
+        "Both" => StickSide::Both,
+        _ => StickSide::Left, // Default fallback
+    }
+}
+
+/// Convert a stick axis enum to its display name.
+pub fn stick_axis_to_name(axis: &StickAxis) -> &'static str {
+    match axis {
+        StickAxis::LeftX => "Left Stick X",
+        StickAxis::LeftY => "Left Stick Y",
+        StickAxis::RightX => "Right Stick X",
+        StickAxis::RightY => "Right Stick Y",
+    }
+}
+
+/// Convert a display name to a stick axis enum.
+pub fn name_to_stick_axis(name: &str) -> Option<StickAxis> {
+    match name {
+        "Left Stick X" => Some(StickAxis::LeftX),
+        "Left Stick Y" => Some(StickAxis::LeftY),
+        "Right Stick X" => Some(StickAxis::RightX),
+        "Right Stick Y" => Some(StickAxis::RightY),
+        _ => None,
+    }
+}
+
+/// Convert an output axis enum to its display name.
+pub fn output_axis_to_name(axis: &OutputAxis) -> &'static str {
+    match axis {
+        OutputAxis::LeftStickX => "Left Stick X",
+        OutputAxis::LeftStickY => "Left Stick Y",
+        OutputAxis::RightStickX => "Right Stick X",
+        OutputAxis::RightStickY => "Right Stick Y",
+        OutputAxis::LeftTrigger => "Left Trigger",
+        OutputAxis::RightTrigger => "Right Trigger",
+    }
+}
+
+/// Convert a display name to an output axis enum.
+pub fn name_to_output_axis(name: &str) -> Option<OutputAxis> {
+    match name {
+        "Left Stick X" => Some(OutputAxis::LeftStickX),
+        "Left Stick Y" => Some(OutputAxis::LeftStickY),
+        "Right Stick X" => Some(OutputAxis::RightStickX),
+        "Right Stick Y" => Some(OutputAxis::RightStickY),
+        "Left Trigger" => Some(OutputAxis::LeftTrigger),
+        "Right Trigger" => Some(OutputAxis::RightTrigger),
+        _ => None,
+    }
+}
+
+/// Convert a SOCD policy enum to its display name.
+pub fn socd_policy_to_name(policy: &SocdPolicy) -> &'static str {
+    match policy {
+        SocdPolicy::Difference => "Difference",
+        SocdPolicy::LastInputWins => "Last Input Wins",
+        SocdPolicy::FirstInputWins => "First Input Wins",
+        SocdPolicy::Neutral => "Neutral",
+        SocdPolicy::DeeperPressWins => "Deeper Press Wins",
+    }
+}
+
+/// Convert a display name to a SOCD policy enum.
+pub fn name_to_socd_policy(name: &str) -> SocdPolicy {
+    match name {
+        "Last Input Wins" => SocdPolicy::LastInputWins,
+        "First Input Wins" => SocdPolicy::FirstInputWins,
+        "Neutral" => SocdPolicy::Neutral,
+        "Deeper Press Wins" => SocdPolicy::DeeperPressWins,
+        _ => SocdPolicy::Difference, // Default fallback
+    }
+}
+
+/// Convert a combine mode enum to its display name.
+pub fn combine_mode_to_name(mode: &CombineMode) -> &'static str {
+    match mode {
+        CombineMode::Max => "Max",
+        CombineMode::SumClamped => "Sum",
+        CombineMode::Average => "Average",
+        CombineMode::Priority => "Priority",
+    }
+}
+
+/// Convert a display name to a combine mode enum.
+pub fn name_to_combine_mode(name: &str) -> CombineMode {
+    match name {
+        "Sum" => CombineMode::SumClamped,
+        "Average" => CombineMode::Average,
+        "Priority" => CombineMode::Priority,
+        _ => CombineMode::Max, // Default fallback
+    }
+}
+
+/// Convert a button behavior enum to its display name.
+pub fn button_behavior_to_name(behavior: &ButtonBehavior) -> &'static str {
+    match behavior {
+        ButtonBehavior::Normal => "Normal",
+        ButtonBehavior::Turbo => "Turbo",
+        ButtonBehavior::Toggle => "Toggle",
+    }
+}
+
+/// Convert a display name to a button behavior enum.
+pub fn name_to_button_behavior(name: &str) -> ButtonBehavior {
+    match name {
+        "Turbo" => ButtonBehavior::Turbo,
+        "Toggle" => ButtonBehavior::Toggle,
+        _ => ButtonBehavior::Normal, // Default fallback
+    }
+}
+
+/// Convert a smoothing filter enum to its display name.
+pub fn smoothing_filter_to_name(filter: &SmoothingFilter) -> &'static str {
+    match filter {
+        SmoothingFilter::None => "None",
+        SmoothingFilter::Ema => "EMA",
+        SmoothingFilter::OneEuro => "One Euro",
+    }
+}
+
+/// Convert a display name to a smoothing filter enum.
+pub fn name_to_smoothing_filter(name: &str) -> SmoothingFilter {
+    match name {
+        "EMA" => SmoothingFilter::Ema,
+        "One Euro" => SmoothingFilter::OneEuro,
+        _ => SmoothingFilter::None, // Default fallback
+    }
+}
+
+/// Convert a hotkey mode enum to its display name.
+pub fn hotkey_mode_to_name(mode: &HotkeyMode) -> &'static str {
+    match mode {
+        HotkeyMode::Switch => "Switch",
+        HotkeyMode::Hold => "Hold",
+    }
+}
+
+/// Convert a display name to a hotkey mode enum.
+pub fn name_to_hotkey_mode(name: &str) -> HotkeyMode {
+    match name {
+        "Hold" => HotkeyMode::Hold,
+        _ => HotkeyMode::Switch, // Default fallback
+    }
+}
+
 pub fn metadata_hotkey_to_struct(raw: &str) -> Option<HotKey> {
     let trimmed = raw.trim();
     if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("none") {
@@ -484,6 +699,13 @@ pub fn get_all_gamepad_control_names() -> Vec<&'static str> {
         GamepadControl::DPadDown,
         GamepadControl::DPadLeft,
         GamepadControl::DPadRight,
+        GamepadControl::Start,
+        GamepadControl::Back,
+        GamepadControl::Guide,
+        GamepadControl::LeftThumb,
+        GamepadControl::RightThumb,
+        GamepadControl::LeftStickAngle,
+        GamepadControl::RightStickAngle,
     ];
 
     // Convert enums to names using the safe conversion function
@@ -495,7 +717,10 @@ pub fn get_all_gamepad_control_names() -> Vec<&'static str> {
 
 #[cfg(test)]
 mod tests {
-    use super::{hotkey_to_metadata_string, metadata_hotkey_to_struct};
+    use super::{
+        get_all_gamepad_control_names, hotkey_to_metadata_string, metadata_hotkey_to_struct,
+        name_to_gamepad_control,
+    };
 
     #[test]
     fn parse_simple_hotkey() {
@@ -524,4 +749,14 @@ mod tests {
         let serialized = hotkey_to_metadata_string(&parsed);
         assert_eq!(serialized, "Ctrl + Shift + F5");
     }
+
+    #[test]
+    fn gamepad_control_names_round_trip() {
+        let names = get_all_gamepad_control_names();
+        assert!(names.contains(&"Guide"));
+        for name in names {
+            let control = name_to_gamepad_control(name).expect("Control expected");
+            assert_eq!(super::gamepad_control_to_name(&control), name);
+        }
+    }
 }
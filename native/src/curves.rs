@@ -1,21 +1,368 @@
-use crate::profile::profiles::{CurveParams, ResponseCurve};
+use crate::profile::profiles::{CurveParams, ResponseCurve, StickShape, StickSide};
 
-// Lookup table resolution for custom curves (256 entries ~1KB).
+// Lookup table resolution for custom and parametric curves (256 entries ~1KB).
 const LUT_SIZE: usize = 256;
 
+// Strength below this is treated as a straight line for exponential/logarithmic curves.
+const MIN_CURVE_STRENGTH: f32 = 1e-3;
+/// Largest parametric strength magnitude; beyond it `exp` overflows and S-curve terms underflow in f32.
+pub const MAX_CURVE_STRENGTH: f32 = 50.0;
+
+// Bisection steps when solving x(t) for Bezier curves (2^-24 precision).
+const BEZIER_SOLVE_ITERATIONS: usize = 24;
+
 /// Trait for applying a response curve.
 pub trait CurveProcessor {
     fn process_input(&self, raw_value: f32) -> f32;
 }
 
-/// Curve implementation supporting linear and custom response curves.
+/// Output remapping applied after the response curve.
+///
+/// `min` acts as an anti-deadzone: any non-zero curve output starts at `min`,
+/// which pushes past the game's own stick deadzone.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutputRange {
+    pub min: f32,
+    pub max: f32,
+    pub gain: f32,
+}
+
+impl OutputRange {
+    pub fn new(min: f32, max: f32, gain: f32) -> Self {
+        let min = min.clamp(0.0, 1.0);
+        Self {
+            min,
+            max: max.clamp(min, 1.0),
+            gain: gain.max(0.0),
+        }
+    }
+
+    /// Scale a curve output in [0.0, 1.0] into [min, max]. Zero stays zero.
+    #[inline(always)]
+    pub fn apply(&self, value: f32) -> f32 {
+        if value <= 0.0 {
+            return 0.0;
+        }
+
+        let scaled = (value * self.gain).min(1.0);
+        self.min + scaled * (self.max - self.min)
+    }
+}
+
+/// Separate engage and release thresholds for the inner dead zone.
+///
+/// A key engages once it reaches `engage` and stays engaged until it drops to
+/// `release` or below, so noise around the threshold no longer toggles the output.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeadZoneHysteresis {
+    pub engage: f32,
+    pub release: f32,
+}
+
+impl DeadZoneHysteresis {
+    /// Smallest output of an engaged key: one step of an 8-bit trigger, so a key
+    /// resting inside the release band never reads as released.
+    pub const MIN_ENGAGED_OUTPUT: f32 = 1.0 / 255.0;
+
+    /// Build a band of `width` below the inner dead zone. Returns `None` when disabled.
+    pub fn new(dead_zone_inner: f32, width: f32) -> Option<Self> {
+        if !width.is_finite() || width <= 0.0 {
+            return None;
+        }
+
+        Some(Self {
+            engage: dead_zone_inner,
+            release: (dead_zone_inner - width).max(0.0),
+        })
+    }
+
+    /// Return the engaged state for a new raw value.
+    #[inline(always)]
+    pub fn update(&self, raw_value: f32, engaged: bool) -> bool {
+        if engaged {
+            raw_value > self.release
+        } else {
+            raw_value >= self.engage
+        }
+    }
+}
+
+/// Analog actuation of a digital button mapping.
+///
+/// The button presses once the raw key depth reaches `press_depth` and
+/// releases once it falls below `release_depth`.
+///
+/// With rapid trigger the button instead releases as soon as the key travels
+/// up by `rapid_trigger` from its deepest point and presses again on the same
+/// amount of downward travel, anywhere in the key range. A fully released key
+/// goes back to pressing at `press_depth`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ButtonActuation {
+    pub press_depth: f32,
+    pub release_depth: f32,
+    pub rapid_trigger: Option<f32>, // Travel sensitivity
+}
+
+impl ButtonActuation {
+    pub fn new(press_depth: f32, release_depth: f32) -> Self {
+        let press_depth = if press_depth.is_finite() {
+            press_depth.clamp(0.01, 1.0)
+        } else {
+            crate::profile::profiles::default_actuation_depth()
+        };
+        let release_depth = if release_depth.is_finite() {
+            release_depth.clamp(0.0, press_depth)
+        } else {
+            press_depth
+        };
+
+        Self {
+            press_depth,
+            release_depth,
+            rapid_trigger: None,
+        }
+    }
+
+    /// Enable rapid trigger with the given travel sensitivity.
+    pub fn with_rapid_trigger(mut self, sensitivity: f32) -> Self {
+        let sensitivity = if sensitivity.is_finite() {
+            sensitivity.clamp(0.01, 1.0)
+        } else {
+            crate::profile::profiles::default_rapid_trigger_sensitivity()
+        };
+        self.rapid_trigger = Some(sensitivity);
+        self
+    }
+
+    /// Return the pressed state for a new raw depth.
+    ///
+    /// `extreme` is the per-key travel state owned by the caller: the deepest
+    /// depth while pressed, the shallowest depth while released (0 at rest).
+    #[inline(always)]
+    pub fn update(&self, raw_value: f32, pressed: bool, extreme: &mut f32) -> bool {
+        let Some(sensitivity) = self.rapid_trigger else {
+            return if pressed {
+                raw_value > 0.0 && raw_value >= self.release_depth
+            } else {
+                raw_value >= self.press_depth
+            };
+        };
+
+        if raw_value <= 0.0 {
+            *extreme = 0.0;
+            return false;
+        }
+
+        if pressed {
+            *extreme = extreme.max(raw_value);
+            if raw_value <= *extreme - sensitivity {
+                *extreme = raw_value;
+                return false;
+            }
+            true
+        } else if *extreme <= 0.0 {
+            // Key came up from rest: regular actuation point.
+            if raw_value >= self.press_depth {
+                *extreme = raw_value;
+                return true;
+            }
+            false
+        } else {
+            *extreme = extreme.min(raw_value);
+            if raw_value >= *extreme + sensitivity {
+                *extreme = raw_value;
+                return true;
+            }
+            false
+        }
+    }
+}
+
+/// Stick shaping applied to a combined (x, y) stick position.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct StickShaper {
+    pub shape: StickShape,
+    pub radial_dead_zone: f32,
+}
+
+impl StickShaper {
+    pub fn new(shape: StickShape, radial_dead_zone: f32) -> Self {
+        let radial_dead_zone = if radial_dead_zone.is_finite() {
+            radial_dead_zone.clamp(0.0, 0.99)
+        } else {
+            0.0
+        };
+
+        Self {
+            shape,
+            radial_dead_zone,
+        }
+    }
+
+    /// Shape a stick position with axes in [-1.0, 1.0].
+    #[inline]
+    pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        let (mut x, mut y) = match self.shape {
+            StickShape::Square | StickShape::CircularClamp => (x, y),
+            StickShape::SquareToCircle => (
+                x * (1.0 - y * y * 0.5).max(0.0).sqrt(),
+                y * (1.0 - x * x * 0.5).max(0.0).sqrt(),
+            ),
+        };
+
+        let magnitude = x.hypot(y);
+        if self.shape != StickShape::Square && magnitude > 1.0 {
+            x /= magnitude;
+            y /= magnitude;
+        }
+
+        if self.radial_dead_zone > 0.0 {
+            let magnitude = x.hypot(y);
+            if magnitude <= self.radial_dead_zone {
+                return (0.0, 0.0);
+            }
+
+            // Rescale so output magnitude starts at 0 at the dead zone edge.
+            let scale =
+                (magnitude - self.radial_dead_zone) / ((1.0 - self.radial_dead_zone) * magnitude);
+            x *= scale;
+            y *= scale;
+        }
+
+        (x.clamp(-1.0, 1.0), y.clamp(-1.0, 1.0))
+    }
+
+    /// Scale a shaped position, shrinking it along its direction when it would
+    /// leave the shape's range instead of clipping each axis.
+    #[inline]
+    pub fn scale(&self, (x, y): (f32, f32), scale: f32) -> (f32, f32) {
+        let (x, y) = (x * scale, y * scale);
+        let extent = match self.shape {
+            StickShape::Square => x.abs().max(y.abs()),
+            StickShape::CircularClamp | StickShape::SquareToCircle => x.hypot(y),
+        };
+
+        if extent > 1.0 {
+            (x / extent, y / extent)
+        } else {
+            (x, y)
+        }
+    }
+}
+
+/// Unit direction of an arbitrary-angle stick mapping.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StickDirection {
+    pub right_stick: bool,
+    pub x: f32,
+    pub y: f32,
+}
+
+impl StickDirection {
+    /// Direction at `degrees` counter-clockwise from stick right (90 = up).
+    pub fn new(right_stick: bool, degrees: f32) -> Self {
+        let degrees = if degrees.is_finite() { degrees } else { 0.0 };
+        let (y, x) = degrees.to_radians().sin_cos();
+        Self { right_stick, x, y }
+    }
+}
+
+/// Routing of the shaped sticks to the gamepad outputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StickLayout {
+    pub swap_sticks: bool,
+    pub invert_left_y: bool,
+    pub invert_right_y: bool,
+}
+
+impl StickLayout {
+    /// Map (left, right) stick positions to the (left, right) outputs.
+    #[inline]
+    pub fn apply(&self, left: (f32, f32), right: (f32, f32)) -> ((f32, f32), (f32, f32)) {
+        let (mut left, mut right) = if self.swap_sticks {
+            (right, left)
+        } else {
+            (left, right)
+        };
+        if self.invert_left_y {
+            left.1 = -left.1;
+        }
+        if self.invert_right_y {
+            right.1 = -right.1;
+        }
+        (left, right)
+    }
+}
+
+/// Stick magnitude scale applied while a modifier key is held.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StickMagnitudeModifier {
+    pub key_codes: [u16; 2], // Left and right variants of the key
+    pub side: StickSide,
+    pub scale: f32,
+}
+
+impl StickMagnitudeModifier {
+    /// Key travel at which a modifier counts as held.
+    pub const ENGAGE_DEPTH: f32 = 0.3;
+
+    pub fn new(key_codes: [u16; 2], side: StickSide, scale: f32) -> Self {
+        let scale = if scale.is_finite() {
+            scale.clamp(0.0, 2.0)
+        } else {
+            1.0
+        };
+        Self {
+            key_codes,
+            side,
+            scale,
+        }
+    }
+
+    /// Combined (left, right) stick scales for the pressed keys, given as (key code, depth).
+    /// Scales of several held modifiers multiply.
+    pub fn stick_scales<I>(modifiers: &[Self], keys: I) -> (f32, f32)
+    where
+        I: Iterator<Item = (u16, f32)> + Clone,
+    {
+        let mut left = 1.0;
+        let mut right = 1.0;
+        for modifier in modifiers {
+            let held = keys.clone().any(|(key_code, depth)| {
+                modifier.key_codes.contains(&key_code) && depth >= Self::ENGAGE_DEPTH
+            });
+            if !held {
+                continue;
+            }
+            if modifier.side != StickSide::Right {
+                left *= modifier.scale;
+            }
+            if modifier.side != StickSide::Left {
+                right *= modifier.scale;
+            }
+        }
+        (left, right)
+    }
+}
+
+impl Default for OutputRange {
+    fn default() -> Self {
+        Self {
+            min: 0.0,
+            max: 1.0,
+            gain: 1.0,
+        }
+    }
+}
+
+/// Curve implementation supporting linear, custom, parametric and Bezier response curves.
 #[derive(Debug, Clone)]
 pub struct UnifiedCurve {
     pub curve_type: ResponseCurve,
     pub params: CurveParams,
     pub dead_zone_inner: f32,
     pub dead_zone_outer: f32,
-    // Pre-computed lookup table for custom curves.
+    // Pre-computed lookup table for non-linear curves.
     lut: Option<Box<[f32; LUT_SIZE]>>,
 }
 
@@ -39,6 +386,71 @@ fn hermite_interp(t: f32, p0: f32, p1: f32, m0: f32, m1: f32, dx: f32) -> f32 {
     h00 * p0 + h10 * dx * m0 + h01 * p1 + h11 * dx * m1
 }
 
+/// Evaluate a parametric curve family at `x` in [0.0, 1.0].
+#[inline]
+fn parametric_value(curve_type: ResponseCurve, strength: f32, x: f32) -> f32 {
+    let x = x.clamp(0.0, 1.0);
+    let strength = strength.clamp(-MAX_CURVE_STRENGTH, MAX_CURVE_STRENGTH);
+
+    let y = match curve_type {
+        ResponseCurve::Power => x.powf(strength.max(MIN_CURVE_STRENGTH)),
+        ResponseCurve::Exponential => {
+            if strength.abs() < MIN_CURVE_STRENGTH {
+                x
+            } else {
+                ((strength * x).exp() - 1.0) / (strength.exp() - 1.0)
+            }
+        }
+        ResponseCurve::SCurve => {
+            let exponent = strength.max(MIN_CURVE_STRENGTH);
+            let rising = x.powf(exponent);
+            let falling = (1.0 - x).powf(exponent);
+            rising / (rising + falling)
+        }
+        ResponseCurve::Logarithmic => {
+            if strength < MIN_CURVE_STRENGTH {
+                x
+            } else {
+                (1.0 + strength * x).ln() / (1.0 + strength).ln()
+            }
+        }
+        ResponseCurve::Linear | ResponseCurve::Custom | ResponseCurve::Bezier => x,
+    };
+
+    y.clamp(0.0, 1.0)
+}
+
+#[inline(always)]
+fn cubic_bezier_1d(t: f32, p1: f32, p2: f32) -> f32 {
+    // Endpoints are fixed at 0 and 1.
+    let u = 1.0 - t;
+    3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t
+}
+
+/// Evaluate a cubic Bezier from (0,0) to (1,1) at `x` in [0.0, 1.0].
+///
+/// Handles are clamped to the unit square, which keeps both x(t) and y(t)
+/// non-decreasing and therefore guarantees a monotonic response.
+fn bezier_value(handles: &[(f32, f32); 2], x: f32) -> f32 {
+    let (x1, y1) = (handles[0].0.clamp(0.0, 1.0), handles[0].1.clamp(0.0, 1.0));
+    let (x2, y2) = (handles[1].0.clamp(0.0, 1.0), handles[1].1.clamp(0.0, 1.0));
+    let x = x.clamp(0.0, 1.0);
+
+    // x(t) is monotonic, so bisection always converges to the single root.
+    let mut low = 0.0f32;
+    let mut high = 1.0f32;
+    for _ in 0..BEZIER_SOLVE_ITERATIONS {
+        let mid = (low + high) * 0.5;
+        if cubic_bezier_1d(mid, x1, x2) < x {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    cubic_bezier_1d((low + high) * 0.5, y1, y2).clamp(0.0, 1.0)
+}
+
 impl UnifiedCurve {
     pub fn new(
         curve_type: ResponseCurve,
@@ -51,18 +463,19 @@ impl UnifiedCurve {
             .sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
 
         let lut = if curve_type == ResponseCurve::Custom && !params.custom_points.is_empty() {
-            let mut table = Box::new([0.0f32; LUT_SIZE]);
-
-            for i in 0..LUT_SIZE {
-                let x = i as f32 / (LUT_SIZE - 1) as f32;
-                table[i] = Self::interpolate_at_point(
+            Some(Self::build_lut(|x| {
+                Self::interpolate_at_point(
                     &params.custom_points,
                     x,
                     params.use_smooth_interpolation,
-                );
-            }
-
-            Some(table)
+                )
+            }))
+        } else if curve_type == ResponseCurve::Bezier {
+            Some(Self::build_lut(|x| bezier_value(&params.bezier_handles, x)))
+        } else if curve_type.is_parametric() {
+            Some(Self::build_lut(|x| {
+                parametric_value(curve_type, params.strength, x)
+            }))
         } else {
             None
         };
@@ -76,6 +489,18 @@ impl UnifiedCurve {
         }
     }
 
+    /// Sample a curve function at evenly spaced points over [0.0, 1.0].
+    fn build_lut<F: Fn(f32) -> f32>(curve_fn: F) -> Box<[f32; LUT_SIZE]> {
+        let mut table = Box::new([0.0f32; LUT_SIZE]);
+
+        for (i, entry) in table.iter_mut().enumerate() {
+            let x = i as f32 / (LUT_SIZE - 1) as f32;
+            *entry = curve_fn(x);
+        }
+
+        table
+    }
+
     /// Interpolate at a specific point for LUT generation.
     #[inline]
     fn interpolate_at_point(points: &[(f32, f32)], x: f32, use_smooth: bool) -> f32 {
@@ -137,7 +562,7 @@ impl UnifiedCurve {
     fn apply_curve(&self, normalized_input: f32) -> f32 {
         match self.curve_type {
             ResponseCurve::Linear => normalized_input,
-            ResponseCurve::Custom => {
+            _ => {
                 if let Some(ref lut) = self.lut {
                     self.lut_lookup(normalized_input, lut)
                 } else {
@@ -147,6 +572,18 @@ impl UnifiedCurve {
         }
     }
 
+    /// Sample the curve (dead zones included) at evenly spaced raw inputs over [0.0, 1.0].
+    pub fn sample(&self, sample_count: usize) -> Vec<(f32, f32)> {
+        let sample_count = sample_count.max(2);
+
+        (0..sample_count)
+            .map(|i| {
+                let x = i as f32 / (sample_count - 1) as f32;
+                (x, self.process_input(x))
+            })
+            .collect()
+    }
+
     /// LUT lookup with linear interpolation between entries.
     #[inline(always)]
     fn lut_lookup(&self, x: f32, lut: &[f32; LUT_SIZE]) -> f32 {
@@ -216,6 +653,7 @@ mod tests {
         let params = CurveParams {
             use_smooth_interpolation: false,
             custom_points: vec![(0.0, 0.0), (0.5, 0.8), (1.0, 1.0)],
+            ..CurveParams::default()
         };
         let curve = UnifiedCurve::new(ResponseCurve::Custom, params, 0.0, 1.0);
 
@@ -232,6 +670,7 @@ mod tests {
         let params = CurveParams {
             use_smooth_interpolation: true,
             custom_points: vec![(0.0, 0.0), (1.0, 1.0)],
+            ..CurveParams::default()
         };
         let curve = UnifiedCurve::new(ResponseCurve::Custom, params, 0.0, 1.0);
 
@@ -244,4 +683,272 @@ mod tests {
         assert!(result_quarter >= 0.0 && result_quarter <= 1.0);
         assert!(result_three_quarters >= 0.0 && result_three_quarters <= 1.0);
     }
+
+    #[test]
+    fn test_power_curve() {
+        let params = CurveParams {
+            strength: 2.0,
+            ..CurveParams::default()
+        };
+        let curve = UnifiedCurve::new(ResponseCurve::Power, params, 0.0, 1.0);
+
+        assert!((curve.process_input(0.5) - 0.25).abs() < 0.01);
+        assert!((curve.process_input(1.0) - 1.0).abs() < 0.001);
+        assert_eq!(curve.process_input(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_parametric_curves_stay_in_range_and_monotonic() {
+        for curve_type in [
+            ResponseCurve::Power,
+            ResponseCurve::Exponential,
+            ResponseCurve::SCurve,
+            ResponseCurve::Logarithmic,
+        ] {
+            for strength in [0.0, 0.5, 2.0, 8.0, 200.0, -200.0, 1e6] {
+                let params = CurveParams {
+                    strength,
+                    ..CurveParams::default()
+                };
+                let curve = UnifiedCurve::new(curve_type, params, 0.0, 1.0);
+
+                let mut previous = 0.0;
+                for i in 0..=100 {
+                    let value = curve.process_input(i as f32 / 100.0);
+                    assert!((0.0..=1.0).contains(&value));
+                    assert!(value + 1e-6 >= previous, "{:?} not monotonic", curve_type);
+                    previous = value;
+                }
+                assert!((curve.process_input(1.0) - 1.0).abs() < 0.001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_s_curve_is_symmetric() {
+        let params = CurveParams {
+            strength: 3.0,
+            ..CurveParams::default()
+        };
+        let curve = UnifiedCurve::new(ResponseCurve::SCurve, params, 0.0, 1.0);
+
+        assert!((curve.process_input(0.5) - 0.5).abs() < 0.01);
+        assert!(curve.process_input(0.25) < 0.25);
+        assert!(curve.process_input(0.75) > 0.75);
+    }
+
+    #[test]
+    fn test_bezier_default_handles_are_linear() {
+        let curve = UnifiedCurve::new(ResponseCurve::Bezier, CurveParams::default(), 0.0, 1.0);
+
+        for i in 0..=10 {
+            let x = i as f32 / 10.0;
+            assert!((curve.process_input(x) - x).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_bezier_extreme_handles_stay_monotonic() {
+        let params = CurveParams {
+            bezier_handles: [(0.9, -0.5), (0.1, 1.5)],
+            ..CurveParams::default()
+        };
+        let curve = UnifiedCurve::new(ResponseCurve::Bezier, params, 0.0, 1.0);
+
+        let mut previous = 0.0;
+        for i in 0..=100 {
+            let value = curve.process_input(i as f32 / 100.0);
+            assert!((0.0..=1.0).contains(&value));
+            assert!(value + 1e-6 >= previous);
+            previous = value;
+        }
+        assert!(curve.process_input(0.0).abs() < 0.001);
+        assert!((curve.process_input(1.0) - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_bezier_ease_in() {
+        let params = CurveParams {
+            bezier_handles: [(0.5, 0.0), (1.0, 0.5)],
+            ..CurveParams::default()
+        };
+        let curve = UnifiedCurve::new(ResponseCurve::Bezier, params, 0.0, 1.0);
+
+        assert!(curve.process_input(0.5) < 0.4);
+    }
+
+    #[test]
+    fn test_output_range_anti_deadzone() {
+        let range = OutputRange::new(0.2, 0.9, 1.0);
+
+        assert_eq!(range.apply(0.0), 0.0);
+        assert!((range.apply(0.01) - 0.207).abs() < 0.001);
+        assert!((range.apply(1.0) - 0.9).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_output_range_gain_saturates_at_max() {
+        let range = OutputRange::new(0.0, 0.8, 2.0);
+
+        assert!((range.apply(0.25) - 0.4).abs() < 0.001);
+        assert!((range.apply(0.75) - 0.8).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_sample_matches_process_input() {
+        let curve = UnifiedCurve::new(ResponseCurve::Power, CurveParams::default(), 0.1, 0.9);
+        let samples = curve.sample(5);
+
+        assert_eq!(samples.len(), 5);
+        assert_eq!(samples[0], (0.0, 0.0));
+        assert_eq!(samples[4].0, 1.0);
+        for (x, y) in samples {
+            assert_eq!(y, curve.process_input(x));
+        }
+    }
+
+    #[test]
+    fn test_dead_zone_hysteresis() {
+        assert!(DeadZoneHysteresis::new(0.1, 0.0).is_none());
+
+        let band = DeadZoneHysteresis::new(0.1, 0.04).unwrap();
+        assert!(!band.update(0.08, false));
+        assert!(band.update(0.1, false));
+        assert!(band.update(0.08, true));
+        assert!(!band.update(0.05, true));
+    }
+
+    #[test]
+    fn test_stick_shaper_diagonals() {
+        let square = StickShaper::new(StickShape::Square, 0.0);
+        assert_eq!(square.apply(1.0, 1.0), (1.0, 1.0));
+
+        for shape in [StickShape::CircularClamp, StickShape::SquareToCircle] {
+            let (x, y) = StickShaper::new(shape, 0.0).apply(1.0, 1.0);
+            assert!((x.hypot(y) - 1.0).abs() < 1e-4);
+            assert!((x - y).abs() < 1e-6);
+        }
+
+        // Cardinal directions are untouched.
+        let (x, y) = StickShaper::new(StickShape::SquareToCircle, 0.0).apply(1.0, 0.0);
+        assert_eq!((x, y), (1.0, 0.0));
+    }
+
+    #[test]
+    fn test_stick_shaper_radial_dead_zone() {
+        let shaper = StickShaper::new(StickShape::CircularClamp, 0.2);
+
+        assert_eq!(shaper.apply(0.1, 0.1), (0.0, 0.0));
+        let (x, y) = shaper.apply(0.6, 0.0);
+        assert!((x - 0.5).abs() < 1e-6);
+        assert_eq!(y, 0.0);
+        let (x, _) = shaper.apply(1.0, 0.0);
+        assert!((x - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_stick_shaper_scale_keeps_direction() {
+        let circle = StickShaper::new(StickShape::CircularClamp, 0.0);
+        let (x, y) = circle.scale((0.8, 0.4), 2.0);
+        assert!((x.hypot(y) - 1.0).abs() < 1e-6);
+        assert!((x - 2.0 * y).abs() < 1e-6);
+        assert_eq!(circle.scale((0.4, 0.2), 0.5), (0.2, 0.1));
+
+        let square = StickShaper::new(StickShape::Square, 0.0);
+        assert_eq!(square.scale((0.8, 0.4), 2.0), (1.0, 0.5));
+    }
+
+    #[test]
+    fn test_stick_direction() {
+        let diagonal = StickDirection::new(false, 45.0);
+        assert!((diagonal.x - 0.70710677).abs() < 1e-6);
+        assert!((diagonal.y - 0.70710677).abs() < 1e-6);
+
+        let down_left = StickDirection::new(true, 225.0);
+        assert!(down_left.right_stick);
+        assert!(down_left.x < 0.0 && down_left.y < 0.0);
+
+        let invalid = StickDirection::new(false, f32::NAN);
+        assert_eq!((invalid.x, invalid.y), (1.0, 0.0));
+    }
+
+    #[test]
+    fn test_stick_layout() {
+        let layout = StickLayout {
+            swap_sticks: true,
+            invert_left_y: false,
+            invert_right_y: true,
+        };
+        let (left, right) = layout.apply((0.1, 0.2), (0.3, 0.4));
+        assert_eq!(left, (0.3, 0.4));
+        assert_eq!(right, (0.1, -0.2));
+        assert_eq!(
+            StickLayout::default().apply((0.1, 0.2), (0.3, 0.4)),
+            ((0.1, 0.2), (0.3, 0.4))
+        );
+    }
+
+    #[test]
+    fn test_stick_magnitude_modifiers() {
+        let modifiers = [
+            StickMagnitudeModifier::new([0xA4, 0xA5], StickSide::Left, 0.4),
+            StickMagnitudeModifier::new([0x10, 0x10], StickSide::Both, 0.5),
+        ];
+
+        let none = StickMagnitudeModifier::stick_scales(&modifiers, [(0x41, 1.0)].into_iter());
+        assert_eq!(none, (1.0, 1.0));
+
+        // Right Alt engages the left stick modifier; a light touch does not.
+        let alt = StickMagnitudeModifier::stick_scales(&modifiers, [(0xA5, 1.0)].into_iter());
+        assert_eq!(alt, (0.4, 1.0));
+        let touch = StickMagnitudeModifier::stick_scales(&modifiers, [(0xA4, 0.1)].into_iter());
+        assert_eq!(touch, (1.0, 1.0));
+
+        let both = StickMagnitudeModifier::stick_scales(
+            &modifiers,
+            [(0xA4, 1.0), (0x10, 1.0)].into_iter(),
+        );
+        assert_eq!(both, (0.2, 0.5));
+    }
+
+    #[test]
+    fn test_button_actuation() {
+        let mut extreme = 0.0;
+        let actuation = ButtonActuation::new(0.5, 0.3);
+        assert!(!actuation.update(0.4, false, &mut extreme));
+        assert!(actuation.update(0.5, false, &mut extreme));
+        assert!(actuation.update(0.35, true, &mut extreme));
+        assert!(!actuation.update(0.2, true, &mut extreme));
+
+        // Release depth never exceeds the press depth.
+        let actuation = ButtonActuation::new(0.4, 0.9);
+        assert_eq!(actuation.release_depth, 0.4);
+        assert!(!actuation.update(0.0, true, &mut extreme));
+    }
+
+    #[test]
+    fn test_rapid_trigger() {
+        let actuation = ButtonActuation::new(0.5, 0.4).with_rapid_trigger(0.1);
+        let mut extreme = 0.0;
+        let mut pressed = false;
+        let mut step = |raw: f32| {
+            pressed = actuation.update(raw, pressed, &mut extreme);
+            pressed
+        };
+
+        // First press at the actuation point.
+        assert!(!step(0.3));
+        assert!(step(0.6));
+        assert!(step(0.8));
+        // Released after 0.1 of upward travel, well above the release depth.
+        assert!(step(0.75));
+        assert!(!step(0.65));
+        // Pressed again on downward travel from the new low point.
+        assert!(!step(0.6));
+        assert!(step(0.72));
+        // A fully released key needs the actuation point again.
+        assert!(!step(0.0));
+        assert!(!step(0.3));
+        assert!(step(0.5));
+    }
 }
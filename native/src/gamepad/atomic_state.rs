@@ -1,11 +1,12 @@
 //! Atomic gamepad state shared between event callbacks and the mapping thread.
 
 use crate::gamepad::vigem_client::XboxButton;
-use std::sync::atomic::{AtomicI16, AtomicU16, AtomicU8, Ordering};
+use std::sync::atomic::{AtomicI16, AtomicU16, AtomicU32, AtomicU8, Ordering};
 
 /// Atomic representation of the current gamepad state.
 pub struct AtomicGamepadState {
-    buttons: AtomicU16, // XButtons bitmask
+    buttons: AtomicU16,       // XButtons bitmask
+    button_resets: AtomicU32, // Incremented by `reset_buttons`
     // Analog controls (updated by mapping thread)
     thumb_lx: AtomicI16, // -32768 to 32767
     thumb_ly: AtomicI16,
@@ -20,6 +21,7 @@ impl AtomicGamepadState {
     pub const fn new() -> Self {
         Self {
             buttons: AtomicU16::new(0),
+            button_resets: AtomicU32::new(0),
             thumb_lx: AtomicI16::new(0),
             thumb_ly: AtomicI16::new(0),
             thumb_rx: AtomicI16::new(0),
@@ -51,11 +53,33 @@ impl AtomicGamepadState {
         }
     }
 
+    /// Press every button in `button_mask`, leaving other buttons untouched.
+    pub fn press_buttons(&self, button_mask: u16) {
+        self.buttons.fetch_or(button_mask, Ordering::Relaxed);
+    }
+
+    /// Release every button in `button_mask`, leaving other buttons untouched.
+    pub fn release_buttons(&self, button_mask: u16) {
+        self.buttons.fetch_and(!button_mask, Ordering::Relaxed);
+    }
+
     /// Replace the current button bitmask.
     pub fn set_buttons(&self, button_mask: u16) {
         self.buttons.store(button_mask, Ordering::Relaxed);
     }
 
+    /// Release every button and signal the reset to the mapping loop, which
+    /// drops its toggle latches and re-presses buttons it still holds.
+    pub fn reset_buttons(&self) {
+        self.buttons.store(0, Ordering::Relaxed);
+        self.button_resets.fetch_add(1, Ordering::Release);
+    }
+
+    /// Number of `reset_buttons` calls so far.
+    pub fn button_resets(&self) -> u32 {
+        self.button_resets.load(Ordering::Acquire)
+    }
+
     /// Update analog stick values atomically, clamped to the valid range.
     pub fn set_sticks(&self, left_x: f64, left_y: f64, right_x: f64, right_y: f64) {
         self.thumb_lx.store(
@@ -114,6 +138,11 @@ impl AtomicGamepadState {
             GamepadControl::DPadDown => Some(XboxButton::DPadDown),
             GamepadControl::DPadLeft => Some(XboxButton::DPadLeft),
             GamepadControl::DPadRight => Some(XboxButton::DPadRight),
+            GamepadControl::Start => Some(XboxButton::Start),
+            GamepadControl::Back => Some(XboxButton::Back),
+            GamepadControl::Guide => Some(XboxButton::Guide),
+            GamepadControl::LeftThumb => Some(XboxButton::LeftThumb),
+            GamepadControl::RightThumb => Some(XboxButton::RightThumb),
             _ => None, // Non-button controls (sticks, triggers)
         }
     }
@@ -110,6 +110,7 @@ pub enum XboxButton {
     RightThumb = 0x0080,
     LeftShoulder = 0x0100,
     RightShoulder = 0x0200,
+    Guide = 0x0400,
     A = 0x1000,
     B = 0x2000,
     X = 0x4000,
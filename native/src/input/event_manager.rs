@@ -13,6 +13,7 @@ use winapi::um::winuser::{
 };
 
 use crate::conversions::{vk, vk_to_key_name};
+use crate::gamepad::XboxButton;
 use crate::profile::profiles::HotKey;
 use log::{debug, error, info};
 
@@ -38,12 +39,14 @@ pub struct KeyInput {
 /// Callback types for different input scenarios.
 pub type HotkeyCallback = Arc<dyn Fn(Uuid, Uuid, &str, &str) + Send + Sync>;
 pub type ProfileCycleCallback = Arc<dyn Fn(Uuid) + Send + Sync>;
+pub type HotkeyHoldCallback = Arc<dyn Fn(Uuid, Uuid, bool) + Send + Sync>; // (profile, sub-profile, is_pressed)
 pub type ButtonCallback = Arc<dyn Fn(bool) + Send + Sync>; // is_pressed -> atomic update
 
 #[derive(Clone)]
 enum HotkeyTarget {
     Switch(HotkeySwitchTarget),
     Cycle(HotkeyCycleTarget),
+    Hold(HotkeyHoldTarget),
 }
 
 #[derive(Clone)]
@@ -61,6 +64,21 @@ struct HotkeyCycleTarget {
     callback: ProfileCycleCallback,
 }
 
+#[derive(Clone)]
+struct HotkeyHoldTarget {
+    profile_id: Uuid,
+    sub_profile_id: Uuid,
+    callback: HotkeyHoldCallback,
+}
+
+/// Chord of the active sub-profile, indexed like `CompiledProfile::chords`.
+struct ChordBinding {
+    key_codes: Vec<[u16; 2]>, // Left and right variants of each key
+    buttons: Vec<XboxButton>,
+    suppress_keys: bool,
+    active: bool, // All keys held, buttons pressed
+}
+
 /// Event-based input manager built on a Windows low-level keyboard hook.
 pub struct EventInputManager {
     // Event processing.
@@ -76,6 +94,12 @@ pub struct EventInputManager {
     // Button callback system - only active for mapped keys.
     button_callbacks: Arc<Mutex<HashMap<u16, ButtonCallback>>>, // vk_code -> atomic callback
 
+    // Macro triggers of the active sub-profile.
+    macro_triggers: Arc<Mutex<HashMap<HotKey, Vec<Uuid>>>>,
+
+    // Chords of the active sub-profile.
+    chord_bindings: Arc<Mutex<Vec<ChordBinding>>>,
+
     // State tracking.
     key_states: Arc<Mutex<HashMap<u16, bool>>>, // vk_code -> is_pressed
     modifier_state: Arc<AtomicU16>,             // Atomic modifier combination for hook
@@ -108,6 +132,8 @@ impl EventInputManager {
             hotkey_mappings: Arc::new(Mutex::new(HashMap::new())),
             hotkey_suppression: Arc::new(AtomicUsize::new(0)),
             button_callbacks: Arc::new(Mutex::new(HashMap::new())),
+            macro_triggers: Arc::new(Mutex::new(HashMap::new())),
+            chord_bindings: Arc::new(Mutex::new(Vec::new())),
             key_states: Arc::new(Mutex::new(HashMap::new())),
             modifier_state: Arc::new(AtomicU16::new(0)),
             events_processed: Arc::new(std::sync::atomic::AtomicU64::new(0)),
@@ -130,6 +156,8 @@ impl EventInputManager {
         let hotkey_mappings = Arc::clone(&self.hotkey_mappings);
         let hotkey_suppression = Arc::clone(&self.hotkey_suppression);
         let button_callbacks = Arc::clone(&self.button_callbacks);
+        let macro_triggers = Arc::clone(&self.macro_triggers);
+        let chord_bindings = Arc::clone(&self.chord_bindings);
         let key_states = Arc::clone(&self.key_states);
         let modifier_state = Arc::clone(&self.modifier_state);
         let events_processed = Arc::clone(&self.events_processed);
@@ -216,6 +244,9 @@ impl EventInputManager {
             debug!("[INPUT] Starting event processing thread");
             is_running.store(true, std::sync::atomic::Ordering::Relaxed);
 
+            // Hold hotkeys currently down, released on key up of their key.
+            let mut held_hotkeys: HashMap<u16, Vec<HotkeyHoldTarget>> = HashMap::new();
+
             // Process incoming key events.
             while let Ok(key_input) = receiver.recv() {
                 events_processed.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
@@ -241,16 +272,35 @@ impl EventInputManager {
                 Self::update_atomic_modifier_state(&modifier_state, key_input.vk_code, is_pressed);
 
                 if process_event {
-                    // Process hotkeys (on key press only)
-                    if matches!(
-                        key_input.event_type,
-                        KeyEvent::KeyDown | KeyEvent::SystemKeyDown
-                    ) {
-                        Self::process_hotkeys(&hotkey_mappings, &hotkey_suppression, &key_input);
+                    // Process hotkeys on key press, release held hotkeys on key up.
+                    if is_pressed {
+                        Self::process_hotkeys(
+                            &hotkey_mappings,
+                            &hotkey_suppression,
+                            &mut held_hotkeys,
+                            &key_input,
+                        );
+                        Self::process_macro_triggers(
+                            &macro_triggers,
+                            &hotkey_suppression,
+                            &key_input,
+                        );
+                    } else {
+                        Self::release_held_hotkeys(&mut held_hotkeys, &key_input);
                     }
 
-                    // Invoke button callbacks after processing the event.
-                    Self::process_button_callbacks(&button_callbacks, &key_input);
+                    // Invoke button callbacks after processing the event, unless a chord
+                    // completed by this key suppresses its own mappings.
+                    let suppressed = Self::process_chords(
+                        &chord_bindings,
+                        &key_states,
+                        &button_callbacks,
+                        &key_input,
+                        is_pressed,
+                    );
+                    if !suppressed {
+                        Self::process_button_callbacks(&button_callbacks, &key_input);
+                    }
                 }
             }
 
@@ -332,6 +382,27 @@ impl EventInputManager {
         Ok(true)
     }
 
+    /// Register hotkey that activates a sub-profile while held.
+    pub fn register_hold_hotkey(
+        &mut self,
+        hotkey: HotKey,
+        profile_id: Uuid,
+        sub_profile_id: Uuid,
+        callback: HotkeyHoldCallback,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        let mut mappings = self.hotkey_mappings.lock().unwrap();
+        let targets = mappings.entry(hotkey).or_default();
+        if targets.iter().any(|target| matches!(target, HotkeyTarget::Hold(existing) if existing.profile_id == profile_id && existing.sub_profile_id == sub_profile_id)) {
+            return Ok(false);
+        }
+        targets.push(HotkeyTarget::Hold(HotkeyHoldTarget {
+            profile_id,
+            sub_profile_id,
+            callback,
+        }));
+        Ok(true)
+    }
+
     /// Remove a previously registered hotkey.
     pub fn unregister_hotkey(&mut self, hotkey: &HotKey) {
         let mut mappings = self.hotkey_mappings.lock().unwrap();
@@ -350,6 +421,7 @@ impl EventInputManager {
             targets.retain(|target| match target {
                 HotkeyTarget::Switch(target) => target.profile_id != profile_id,
                 HotkeyTarget::Cycle(target) => target.profile_id != profile_id,
+                HotkeyTarget::Hold(target) => target.profile_id != profile_id,
             });
             !targets.is_empty()
         });
@@ -374,7 +446,7 @@ impl EventInputManager {
         }
     }
 
-    /// Update button callbacks based on current profile mappings.
+    /// Update button callbacks, macro triggers and chords based on current profile mappings.
     /// Only registers callbacks for keys that are mapped to digital buttons.
     pub fn update_button_callbacks(
         &mut self,
@@ -387,17 +459,60 @@ impl EventInputManager {
         callbacks.clear();
 
         // Pre-register callbacks only for keys mapped to digital buttons.
-        for (vk_code, compiled_mapping) in &compiled_profile.mappings {
-            if let Some(xbox_button) = AtomicGamepadState::gamepad_control_to_xbox_button(
-                &compiled_mapping.gamepad_control,
-            ) {
-                // Create a callback that directly updates the atomic state.
-                let callback: ButtonCallback = Arc::new(move |is_pressed: bool| {
-                    ATOMIC_GAMEPAD_STATE.set_button(xbox_button, is_pressed);
-                });
-                callbacks.insert(*vk_code, callback);
+        // Analog-actuated buttons are driven by the mapping loop instead.
+        for (vk_code, outputs) in &compiled_profile.mappings {
+            let xbox_buttons: Vec<_> = outputs
+                .iter()
+                .filter(|compiled_mapping| compiled_mapping.actuation.is_none())
+                .filter_map(|compiled_mapping| {
+                    AtomicGamepadState::gamepad_control_to_xbox_button(
+                        &compiled_mapping.gamepad_control,
+                    )
+                })
+                .collect();
+
+            if xbox_buttons.is_empty() {
+                continue;
             }
+
+            // Create a callback that directly updates the atomic state.
+            let callback: ButtonCallback = Arc::new(move |is_pressed: bool| {
+                for xbox_button in &xbox_buttons {
+                    ATOMIC_GAMEPAD_STATE.set_button(*xbox_button, is_pressed);
+                }
+            });
+            callbacks.insert(*vk_code, callback);
         }
+        drop(callbacks);
+
+        // Macros are queued here and run by the mapping loop.
+        let mut triggers = self.macro_triggers.lock().unwrap();
+        triggers.clear();
+        for gamepad_macro in &compiled_profile.macros {
+            triggers
+                .entry(gamepad_macro.trigger.clone())
+                .or_default()
+                .push(gamepad_macro.id);
+        }
+        drop(triggers);
+
+        // Chord analog outputs are driven by the mapping loop while held.
+        let mut chords = self.chord_bindings.lock().unwrap();
+        crate::mapping::clear_active_chords();
+        *chords = compiled_profile
+            .chords
+            .iter()
+            .map(|chord| ChordBinding {
+                key_codes: chord.key_codes.clone(),
+                buttons: chord
+                    .outputs
+                    .iter()
+                    .filter_map(AtomicGamepadState::gamepad_control_to_xbox_button)
+                    .collect(),
+                suppress_keys: chord.suppress_keys,
+                active: false,
+            })
+            .collect();
     }
 
     /// Check if key is currently pressed.
@@ -449,6 +564,7 @@ impl EventInputManager {
         if let Some(handle) = self.processing_thread.take() {
             let _ = handle.join();
         }
+        crate::mapping::clear_active_chords();
 
         info!("[INPUT] Event-based input system stopped");
     }
@@ -510,6 +626,7 @@ impl EventInputManager {
     fn process_hotkeys(
         hotkey_mappings: &Arc<Mutex<HashMap<HotKey, Vec<HotkeyTarget>>>>,
         hotkey_suppression: &Arc<AtomicUsize>,
+        held_hotkeys: &mut HashMap<u16, Vec<HotkeyHoldTarget>>,
         key_input: &KeyInput,
     ) {
         if hotkey_suppression.load(AtomicOrdering::Relaxed) > 0 {
@@ -533,6 +650,13 @@ impl EventInputManager {
                         HotkeyTarget::Cycle(target) => {
                             (target.callback)(target.profile_id);
                         }
+                        HotkeyTarget::Hold(target) => {
+                            (target.callback)(target.profile_id, target.sub_profile_id, true);
+                            held_hotkeys
+                                .entry(key_input.vk_code)
+                                .or_default()
+                                .push(target.clone());
+                        }
                     }
                 }
                 // Hotkey executed.
@@ -541,6 +665,117 @@ impl EventInputManager {
         }
     }
 
+    /// Release hold hotkeys of a key, even if the hotkey was removed or suspended meanwhile.
+    fn release_held_hotkeys(
+        held_hotkeys: &mut HashMap<u16, Vec<HotkeyHoldTarget>>,
+        key_input: &KeyInput,
+    ) {
+        if let Some(targets) = held_hotkeys.remove(&key_input.vk_code) {
+            for target in targets.iter().rev() {
+                (target.callback)(target.profile_id, target.sub_profile_id, false);
+            }
+        }
+    }
+
+    /// Queue the macros triggered by a key press for the mapping loop.
+    fn process_macro_triggers(
+        macro_triggers: &Arc<Mutex<HashMap<HotKey, Vec<Uuid>>>>,
+        hotkey_suppression: &Arc<AtomicUsize>,
+        key_input: &KeyInput,
+    ) {
+        if hotkey_suppression.load(AtomicOrdering::Relaxed) > 0 {
+            return;
+        }
+        let triggers = macro_triggers.lock().unwrap();
+        for (trigger, macro_ids) in triggers.iter() {
+            if trigger.get_vk_code() == key_input.vk_code
+                && trigger.modifiers as u16 == key_input.modifiers
+            {
+                for macro_id in macro_ids {
+                    crate::mapping::request_macro(*macro_id);
+                }
+            }
+        }
+    }
+
+    /// Hold chords completed by a key press and release those broken by a key
+    /// release, pressing and releasing their buttons. Held chords are published
+    /// for the mapping loop, which drives their analog outputs.
+    ///
+    /// Returns whether the pressed key's own button mappings are suppressed. The
+    /// other keys of a suppressing chord have their buttons released; they stay
+    /// released until pressed again.
+    fn process_chords(
+        chord_bindings: &Arc<Mutex<Vec<ChordBinding>>>,
+        key_states: &Arc<Mutex<HashMap<u16, bool>>>,
+        button_callbacks: &Arc<Mutex<HashMap<u16, ButtonCallback>>>,
+        key_input: &KeyInput,
+        is_pressed: bool,
+    ) -> bool {
+        use crate::ATOMIC_GAMEPAD_STATE;
+
+        let mut chords = chord_bindings.lock().unwrap();
+        let vk_code = key_input.vk_code;
+        let mut suppressed = false;
+
+        if is_pressed {
+            let states = key_states.lock().unwrap();
+            for (index, chord) in chords.iter_mut().enumerate() {
+                if chord.active
+                    || !chord
+                        .key_codes
+                        .iter()
+                        .any(|variants| variants.contains(&vk_code))
+                    || !chord.key_codes.iter().all(|variants| {
+                        variants
+                            .iter()
+                            .any(|key_code| states.get(key_code).copied().unwrap_or(false))
+                    })
+                {
+                    continue;
+                }
+
+                chord.active = true;
+                crate::mapping::set_chord_active(index, true);
+                if chord.suppress_keys {
+                    suppressed = true;
+                    let callbacks = button_callbacks.lock().unwrap();
+                    for key_code in chord.key_codes.iter().flatten() {
+                        if *key_code == vk_code {
+                            continue;
+                        }
+                        if let Some(callback) = callbacks.get(key_code) {
+                            callback(false);
+                        }
+                    }
+                }
+                for button in &chord.buttons {
+                    ATOMIC_GAMEPAD_STATE.set_button(*button, true);
+                }
+            }
+        } else {
+            // A key is released once neither of its variants is held.
+            let states = key_states.lock().unwrap();
+            for (index, chord) in chords.iter_mut().enumerate() {
+                let released = chord.key_codes.iter().any(|variants| {
+                    variants.contains(&vk_code)
+                        && !variants
+                            .iter()
+                            .any(|key_code| states.get(key_code).copied().unwrap_or(false))
+                });
+                if chord.active && released {
+                    chord.active = false;
+                    crate::mapping::set_chord_active(index, false);
+                    for button in &chord.buttons {
+                        ATOMIC_GAMEPAD_STATE.set_button(*button, false);
+                    }
+                }
+            }
+        }
+
+        suppressed
+    }
+
     /// Process button callbacks for keys with registered handlers.
     fn process_button_callbacks(
         callbacks: &Arc<Mutex<HashMap<u16, ButtonCallback>>>,
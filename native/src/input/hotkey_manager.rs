@@ -4,7 +4,7 @@ use std::time::Instant;
 
 use crate::conversions::metadata_hotkey_to_struct;
 use crate::profile::{
-    update_systems_after_profile_switch, ProfileError, ProfileManager, ProfileMetadata,
+    update_systems_after_profile_switch, HotkeyMode, ProfileError, ProfileManager, ProfileMetadata,
     SubProfileMetadata,
 };
 use crate::ui_notifier::notify_sub_profile_switch;
@@ -12,12 +12,13 @@ use crate::{EVENT_INPUT_MANAGER, PROFILE_MANAGER};
 use log::warn;
 use uuid::Uuid;
 
-use super::{EventInputManager, HotkeyCallback, ProfileCycleCallback};
+use super::{EventInputManager, HotkeyCallback, HotkeyHoldCallback, ProfileCycleCallback};
 
 /// Registers and executes profile/sub-profile hotkeys based on stored metadata.
 pub struct HotkeyManager {
     switch_callback: HotkeyCallback,
     cycle_callback: ProfileCycleCallback,
+    hold_callback: HotkeyHoldCallback,
 }
 
 impl HotkeyManager {
@@ -150,9 +151,46 @@ impl HotkeyManager {
             }
         });
 
+        let hold_callback: HotkeyHoldCallback =
+            Arc::new(move |profile_id, sub_profile_id, is_pressed| {
+                let result = {
+                    let mut manager_guard = PROFILE_MANAGER.lock().unwrap();
+                    if let Some(ref mut manager) = *manager_guard {
+                        if is_pressed {
+                            manager
+                                .activate_momentary_sub_profile(&profile_id, &sub_profile_id)
+                                .map(|activated| activated.then_some(sub_profile_id))
+                        } else {
+                            manager.release_momentary_sub_profile(&profile_id, &sub_profile_id)
+                        }
+                    } else {
+                        Err(ProfileError::NoProfileLoaded)
+                    }
+                };
+
+                match result {
+                    Ok(Some(active_sub_profile_id)) => {
+                        update_systems_after_profile_switch();
+                        notify_sub_profile_switch(profile_id, active_sub_profile_id);
+
+                        #[cfg(debug_assertions)]
+                        warn!(
+                            "[HOTKEY-CALLBACK] Hold {} -> sub-profile {}",
+                            if is_pressed { "pressed" } else { "released" },
+                            active_sub_profile_id
+                        );
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        warn!("[HOTKEY-CALLBACK] Hold switch failed: {}", e);
+                    }
+                }
+            });
+
         Self {
             switch_callback,
             cycle_callback,
+            hold_callback,
         }
     }
 
@@ -178,14 +216,23 @@ impl HotkeyManager {
         for sub_meta in sub_metas {
             if let Some(hotkey_str) = sub_meta.hotkey.as_deref() {
                 if let Some(hotkey) = metadata_hotkey_to_struct(hotkey_str) {
-                    if let Ok(true) = event_manager.register_switch_hotkey(
-                        hotkey,
-                        profile_meta.id,
-                        profile_meta.name.clone(),
-                        sub_meta.id,
-                        sub_meta.name.clone(),
-                        Arc::clone(&self.switch_callback),
-                    ) {
+                    let result = match sub_meta.hotkey_mode {
+                        HotkeyMode::Switch => event_manager.register_switch_hotkey(
+                            hotkey,
+                            profile_meta.id,
+                            profile_meta.name.clone(),
+                            sub_meta.id,
+                            sub_meta.name.clone(),
+                            Arc::clone(&self.switch_callback),
+                        ),
+                        HotkeyMode::Hold => event_manager.register_hold_hotkey(
+                            hotkey,
+                            profile_meta.id,
+                            sub_meta.id,
+                            Arc::clone(&self.hold_callback),
+                        ),
+                    };
+                    if let Ok(true) = result {
                         registered += 1;
                     }
                 }
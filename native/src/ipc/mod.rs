@@ -5,7 +5,9 @@ pub mod protocol;
 pub mod server;
 
 pub use protocol::{
-    IpcCommand, IpcResponse, MappingInfo, ProfileMetadata, SubProfileMetadata, UiEventData,
+    ChordInfo, CurveDefinition, CurvePresetInfo, IpcCommand, IpcResponse, MacroInfo,
+    MacroStepInfo, MappingInfo, ProfileMetadata, SubProfileMetadata, SubProfileSettingsInfo,
+    UiEventData,
 };
 pub use server::IpcServer;
 
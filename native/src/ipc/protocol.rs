@@ -39,17 +39,67 @@ pub enum IpcCommandType {
     GetCurrentMappingInfo {
         index: u32,
     },
+    SampleCurve {
+        curve: CurveDefinition,
+        sample_count: u32,
+    },
+    ValidateMapping {
+        mapping: MappingInfo,
+    },
+    ValidateCurrentMappings,
+    SetStrictValidation {
+        enabled: bool,
+    },
+
+    // Sub-profile settings (current sub-profile)
+    GetSubProfileSettings,
+    SetSubProfileSettings {
+        settings: SubProfileSettingsInfo,
+    },
+
+    // Curve presets (current profile)
+    GetCurvePresets,
+    SetCurvePreset {
+        preset: CurvePresetInfo,
+    },
+    DeleteCurvePreset {
+        preset_id: [u8; 16],
+    },
+
+    // Macros (current sub-profile)
+    GetMacros,
+    SetMacro {
+        macro_info: MacroInfo,
+    },
+    DeleteMacro {
+        macro_id: [u8; 16],
+    },
+
+    // Chords (current sub-profile)
+    GetChords,
+    SetChord {
+        chord: ChordInfo,
+    },
+    DeleteChord {
+        chord_id: [u8; 16],
+    },
 
     // Mapping CRUD
     SetMapping {
         profile_id: [u8; 16],
         sub_profile_id: [u8; 16],
         mapping: MappingInfo,
+        // false adds or updates one output while keeping the key's other outputs
+        #[serde(default = "default_replace_other_outputs")]
+        replace_other_outputs: bool,
     },
     RemoveMapping {
         profile_id: [u8; 16],
         sub_profile_id: [u8; 16],
         key_name: String,
+        // Remove a single output instead of every output of the key
+        #[serde(default)]
+        gamepad_control: Option<String>,
     },
 
     // Profile CRUD
@@ -93,6 +143,8 @@ pub enum IpcCommandType {
         profile_id: [u8; 16],
         sub_id: [u8; 16],
         hotkey: String,
+        #[serde(default)]
+        hotkey_mode: Option<String>, // "Switch" or "Hold", unchanged if omitted
     },
 
     // Import/Export
@@ -125,7 +177,14 @@ pub enum IpcCommandType {
     ResumeHotkeys,
 }
 
-use crate::api::types::{MappingDto, ProfileMetadataDto, SubProfileMetadataDto};
+fn default_replace_other_outputs() -> bool {
+    true
+}
+
+use crate::api::types::{
+    ChordDto, CurveDefinitionDto, CurvePresetDto, MacroDto, MacroStepDto, MappingDto,
+    ProfileMetadataDto, SubProfileMetadataDto, SubProfileSettingsDto,
+};
 
 /// Wrapper for IPC responses with correlation ID
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -166,6 +225,36 @@ pub enum IpcResponseType {
     MappingInfo {
         data: MappingInfo,
     },
+    CurveSamples {
+        samples: Vec<(f32, f32)>,
+    },
+    MappingValidation {
+        data: crate::profile::MappingValidation,
+    },
+    MappingValidations {
+        data: Vec<crate::profile::MappingValidation>,
+    },
+    SubProfileSettings {
+        data: SubProfileSettingsInfo,
+    },
+    CurvePresets {
+        data: Vec<CurvePresetInfo>,
+    },
+    CurvePresetSaved {
+        id: [u8; 16],
+    },
+    Macros {
+        data: Vec<MacroInfo>,
+    },
+    MacroSaved {
+        id: [u8; 16],
+    },
+    Chords {
+        data: Vec<ChordInfo>,
+    },
+    ChordSaved {
+        id: [u8; 16],
+    },
     PerformanceMetrics {
         data: crate::api::types::PerformanceMetrics,
     },
@@ -213,10 +302,24 @@ pub struct SubProfileMetadata {
     pub name: String,
     pub description: String,
     pub hotkey: String,
+    #[serde(default = "default_hotkey_mode_name")]
+    pub hotkey_mode: String, // "Switch", "Hold"
     pub created_at: i64,
     pub modified_at: i64,
 }
 
+fn default_hotkey_mode_name() -> String {
+    "Switch".to_string()
+}
+
+fn default_button_behavior_name() -> String {
+    "Normal".to_string()
+}
+
+fn default_smoothing_name() -> String {
+    "None".to_string()
+}
+
 impl From<SubProfileMetadataDto> for SubProfileMetadata {
     fn from(dto: SubProfileMetadataDto) -> Self {
         Self {
@@ -225,6 +328,7 @@ impl From<SubProfileMetadataDto> for SubProfileMetadata {
             name: dto.name,
             description: dto.description,
             hotkey: dto.hotkey.unwrap_or_else(|| "None".to_string()),
+            hotkey_mode: dto.hotkey_mode,
             created_at: dto.created_at as i64,
             modified_at: dto.modified_at as i64,
         }
@@ -239,9 +343,57 @@ pub struct MappingInfo {
     pub response_curve: String,
     pub dead_zone_inner: f32,
     pub dead_zone_outer: f32,
+    #[serde(default)]
+    pub dead_zone_hysteresis: f32, // Release band below the inner dead zone
     pub use_smooth_curve: bool,
     pub custom_point_count: u32,
     pub custom_points: Vec<(f32, f32)>, // Up to 16 points
+    #[serde(default = "crate::profile::profiles::default_curve_strength")]
+    pub curve_strength: f32, // Exponent/strength for parametric curves
+    #[serde(default = "crate::profile::profiles::default_bezier_handles")]
+    pub bezier_handles: [(f32, f32); 2], // Bezier control handles (P1, P2)
+    #[serde(default)]
+    pub curve_preset_id: Option<[u8; 16]>, // Shared curve preset, overrides the curve fields
+    #[serde(default)]
+    pub output_min: f32, // Anti-deadzone
+    #[serde(default = "crate::profile::profiles::default_output_max")]
+    pub output_max: f32,
+    #[serde(default = "crate::profile::profiles::default_output_gain")]
+    pub output_gain: f32,
+    #[serde(default)]
+    pub analog_actuation: bool, // Buttons: fire from the analog depth
+    #[serde(default = "crate::profile::profiles::default_actuation_depth")]
+    pub actuation_depth: f32,
+    #[serde(default = "crate::profile::profiles::default_release_depth")]
+    pub release_depth: f32,
+    #[serde(default)]
+    pub rapid_trigger: bool,
+    #[serde(default = "crate::profile::profiles::default_rapid_trigger_sensitivity")]
+    pub rapid_trigger_sensitivity: f32,
+    #[serde(default = "default_button_behavior_name")]
+    pub button_behavior: String, // "Normal", "Turbo", "Toggle"
+    #[serde(default = "crate::profile::profiles::default_turbo_rate")]
+    pub turbo_rate: f32, // Turbo presses per second
+    #[serde(default)]
+    pub stick_angle: f32, // Stick angle controls: degrees counter-clockwise from right
+    #[serde(default = "default_smoothing_name")]
+    pub smoothing: String, // "None", "EMA", "One Euro"
+    #[serde(default = "crate::profile::profiles::default_smoothing_cutoff")]
+    pub smoothing_cutoff: f32, // Hz
+    #[serde(default = "crate::profile::profiles::default_smoothing_beta")]
+    pub smoothing_beta: f32,
+    #[serde(default)]
+    pub invert_output: bool, // Analog controls: released key = full output
+    #[serde(default)]
+    pub invert_direction: bool, // Stick controls: push the opposite direction
+    #[serde(default)]
+    pub acceleration: bool, // Stick controls: gain ramps up while held
+    #[serde(default = "crate::profile::profiles::default_acceleration_onset_ms")]
+    pub acceleration_onset_ms: u32,
+    #[serde(default = "crate::profile::profiles::default_acceleration_ramp_ms")]
+    pub acceleration_ramp_ms: u32,
+    #[serde(default = "crate::profile::profiles::default_acceleration_max_boost")]
+    pub acceleration_max_boost: f32, // Extra gain at full boost
     pub created_at: i64,
 }
 
@@ -260,9 +412,33 @@ impl From<MappingDto> for MappingInfo {
             response_curve: dto.response_curve,
             dead_zone_inner: dto.dead_zone_inner,
             dead_zone_outer: dto.dead_zone_outer,
+            dead_zone_hysteresis: dto.dead_zone_hysteresis,
             use_smooth_curve: dto.use_smooth_curve,
             custom_point_count,
             custom_points,
+            curve_strength: dto.curve_strength,
+            bezier_handles: dto.bezier_handles,
+            curve_preset_id: dto.curve_preset_id,
+            output_min: dto.output_min,
+            output_max: dto.output_max,
+            output_gain: dto.output_gain,
+            analog_actuation: dto.analog_actuation,
+            actuation_depth: dto.actuation_depth,
+            release_depth: dto.release_depth,
+            rapid_trigger: dto.rapid_trigger,
+            rapid_trigger_sensitivity: dto.rapid_trigger_sensitivity,
+            button_behavior: dto.button_behavior,
+            turbo_rate: dto.turbo_rate,
+            stick_angle: dto.stick_angle,
+            smoothing: dto.smoothing,
+            smoothing_cutoff: dto.smoothing_cutoff,
+            smoothing_beta: dto.smoothing_beta,
+            invert_output: dto.invert_output,
+            invert_direction: dto.invert_direction,
+            acceleration: dto.acceleration,
+            acceleration_onset_ms: dto.acceleration_onset_ms,
+            acceleration_ramp_ms: dto.acceleration_ramp_ms,
+            acceleration_max_boost: dto.acceleration_max_boost,
             created_at: dto.created_at as i64,
         }
     }
@@ -283,9 +459,301 @@ impl From<MappingInfo> for MappingDto {
             response_curve: info.response_curve,
             dead_zone_inner: info.dead_zone_inner,
             dead_zone_outer: info.dead_zone_outer,
+            dead_zone_hysteresis: info.dead_zone_hysteresis,
             use_smooth_curve: info.use_smooth_curve,
             custom_point_count,
             custom_points,
+            curve_strength: info.curve_strength,
+            bezier_handles: info.bezier_handles,
+            curve_preset_id: info.curve_preset_id,
+            output_min: info.output_min,
+            output_max: info.output_max,
+            output_gain: info.output_gain,
+            analog_actuation: info.analog_actuation,
+            actuation_depth: info.actuation_depth,
+            release_depth: info.release_depth,
+            rapid_trigger: info.rapid_trigger,
+            rapid_trigger_sensitivity: info.rapid_trigger_sensitivity,
+            button_behavior: info.button_behavior,
+            turbo_rate: info.turbo_rate,
+            stick_angle: info.stick_angle,
+            smoothing: info.smoothing,
+            smoothing_cutoff: info.smoothing_cutoff,
+            smoothing_beta: info.smoothing_beta,
+            invert_output: info.invert_output,
+            invert_direction: info.invert_direction,
+            acceleration: info.acceleration,
+            acceleration_onset_ms: info.acceleration_onset_ms,
+            acceleration_ramp_ms: info.acceleration_ramp_ms,
+            acceleration_max_boost: info.acceleration_max_boost,
+            created_at: info.created_at as u64,
+        }
+    }
+}
+
+/// Curve definition structure for IPC preview sampling
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurveDefinition {
+    pub response_curve: String,
+    pub dead_zone_inner: f32,
+    pub dead_zone_outer: f32,
+    pub use_smooth_curve: bool,
+    pub custom_points: Vec<(f32, f32)>, // Up to 16 points
+    #[serde(default = "crate::profile::profiles::default_curve_strength")]
+    pub curve_strength: f32,
+    #[serde(default = "crate::profile::profiles::default_bezier_handles")]
+    pub bezier_handles: [(f32, f32); 2],
+    #[serde(default)]
+    pub output_min: f32,
+    #[serde(default = "crate::profile::profiles::default_output_max")]
+    pub output_max: f32,
+    #[serde(default = "crate::profile::profiles::default_output_gain")]
+    pub output_gain: f32,
+}
+
+impl From<CurveDefinition> for CurveDefinitionDto {
+    fn from(info: CurveDefinition) -> Self {
+        Self {
+            response_curve: info.response_curve,
+            dead_zone_inner: info.dead_zone_inner,
+            dead_zone_outer: info.dead_zone_outer,
+            use_smooth_curve: info.use_smooth_curve,
+            custom_points: info.custom_points.into_iter().take(16).collect(),
+            curve_strength: info.curve_strength,
+            bezier_handles: info.bezier_handles,
+            output_min: info.output_min,
+            output_max: info.output_max,
+            output_gain: info.output_gain,
+        }
+    }
+}
+
+/// Sub-profile processing settings for IPC
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubProfileSettingsInfo {
+    #[serde(default = "default_stick_shape_name")]
+    pub stick_shape: String, // "Square", "Circular Clamp", "Square To Circle"
+    #[serde(default)]
+    pub radial_dead_zone: f32,
+    #[serde(default = "default_socd_policy_name")]
+    pub socd_policy: String, // "Difference", "Last Input Wins", "First Input Wins", "Neutral", "Deeper Press Wins"
+    #[serde(default)]
+    pub combine_modes: Vec<(String, String)>, // (gamepad control, "Max" | "Sum" | "Average" | "Priority")
+    #[serde(default)]
+    pub stick_modifiers: Vec<(String, String, f32)>, // (key name, "Left" | "Right" | "Both", scale)
+    #[serde(default)]
+    pub steering: Vec<(String, f32, f32)>, // ("Left Stick X" ..., steer rate, return rate) per second
+    #[serde(default)]
+    pub slew_rates: Vec<(String, f32)>, // ("Left Stick X" ... "Right Trigger", maximum change per second)
+    #[serde(default)]
+    pub swap_sticks: bool,
+    #[serde(default)]
+    pub invert_left_y: bool, // Output stick, after swapping
+    #[serde(default)]
+    pub invert_right_y: bool,
+}
+
+fn default_stick_shape_name() -> String {
+    "Square".to_string()
+}
+
+fn default_socd_policy_name() -> String {
+    "Difference".to_string()
+}
+
+impl From<SubProfileSettingsDto> for SubProfileSettingsInfo {
+    fn from(dto: SubProfileSettingsDto) -> Self {
+        Self {
+            stick_shape: dto.stick_shape,
+            radial_dead_zone: dto.radial_dead_zone,
+            socd_policy: dto.socd_policy,
+            combine_modes: dto.combine_modes,
+            stick_modifiers: dto.stick_modifiers,
+            steering: dto.steering,
+            slew_rates: dto.slew_rates,
+            swap_sticks: dto.swap_sticks,
+            invert_left_y: dto.invert_left_y,
+            invert_right_y: dto.invert_right_y,
+        }
+    }
+}
+
+impl From<SubProfileSettingsInfo> for SubProfileSettingsDto {
+    fn from(info: SubProfileSettingsInfo) -> Self {
+        Self {
+            stick_shape: info.stick_shape,
+            radial_dead_zone: info.radial_dead_zone,
+            socd_policy: info.socd_policy,
+            combine_modes: info.combine_modes,
+            stick_modifiers: info.stick_modifiers,
+            steering: info.steering,
+            slew_rates: info.slew_rates,
+            swap_sticks: info.swap_sticks,
+            invert_left_y: info.invert_left_y,
+            invert_right_y: info.invert_right_y,
+        }
+    }
+}
+
+/// Curve preset structure for IPC
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurvePresetInfo {
+    #[serde(default)]
+    pub id: [u8; 16], // All zeros creates a new preset
+    pub name: String,
+    pub response_curve: String,
+    #[serde(default)]
+    pub use_smooth_curve: bool,
+    #[serde(default)]
+    pub custom_points: Vec<(f32, f32)>, // Up to 16 points
+    #[serde(default = "crate::profile::profiles::default_curve_strength")]
+    pub curve_strength: f32,
+    #[serde(default = "crate::profile::profiles::default_bezier_handles")]
+    pub bezier_handles: [(f32, f32); 2],
+    #[serde(default)]
+    pub created_at: i64,
+    #[serde(default)]
+    pub modified_at: i64,
+}
+
+impl From<CurvePresetDto> for CurvePresetInfo {
+    fn from(dto: CurvePresetDto) -> Self {
+        Self {
+            id: dto.id,
+            name: dto.name,
+            response_curve: dto.response_curve,
+            use_smooth_curve: dto.use_smooth_curve,
+            custom_points: dto.custom_points.into_iter().take(16).collect(),
+            curve_strength: dto.curve_strength,
+            bezier_handles: dto.bezier_handles,
+            created_at: dto.created_at as i64,
+            modified_at: dto.modified_at as i64,
+        }
+    }
+}
+
+impl From<CurvePresetInfo> for CurvePresetDto {
+    fn from(info: CurvePresetInfo) -> Self {
+        Self {
+            id: info.id,
+            name: info.name,
+            response_curve: info.response_curve,
+            use_smooth_curve: info.use_smooth_curve,
+            custom_points: info.custom_points.into_iter().take(16).collect(),
+            curve_strength: info.curve_strength,
+            bezier_handles: info.bezier_handles,
+            created_at: info.created_at as u64,
+            modified_at: info.modified_at as u64,
+        }
+    }
+}
+
+/// Gamepad macro structure for IPC
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MacroInfo {
+    #[serde(default)]
+    pub id: [u8; 16], // All zeros creates a new macro
+    pub name: String,
+    pub trigger: String, // Hotkey string, e.g. "Ctrl+F1"
+    #[serde(default)]
+    pub steps: Vec<MacroStepInfo>,
+    #[serde(default)]
+    pub created_at: i64,
+}
+
+/// Macro step structure for IPC
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MacroStepInfo {
+    pub action: String, // "Press", "Release", "Set" or "Wait"
+    #[serde(default)]
+    pub control: String,
+    #[serde(default)]
+    pub value: f32,
+    #[serde(default)]
+    pub duration_ms: u32,
+}
+
+impl From<MacroStepDto> for MacroStepInfo {
+    fn from(dto: MacroStepDto) -> Self {
+        Self {
+            action: dto.action,
+            control: dto.control,
+            value: dto.value,
+            duration_ms: dto.duration_ms,
+        }
+    }
+}
+
+impl From<MacroStepInfo> for MacroStepDto {
+    fn from(info: MacroStepInfo) -> Self {
+        Self {
+            action: info.action,
+            control: info.control,
+            value: info.value,
+            duration_ms: info.duration_ms,
+        }
+    }
+}
+
+impl From<MacroDto> for MacroInfo {
+    fn from(dto: MacroDto) -> Self {
+        Self {
+            id: dto.id,
+            name: dto.name,
+            trigger: dto.trigger,
+            steps: dto.steps.into_iter().map(MacroStepInfo::from).collect(),
+            created_at: dto.created_at as i64,
+        }
+    }
+}
+
+impl From<MacroInfo> for MacroDto {
+    fn from(info: MacroInfo) -> Self {
+        Self {
+            id: info.id,
+            name: info.name,
+            trigger: info.trigger,
+            steps: info.steps.into_iter().map(MacroStepDto::from).collect(),
+            created_at: info.created_at as u64,
+        }
+    }
+}
+
+/// Chord mapping structure for IPC
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChordInfo {
+    #[serde(default)]
+    pub id: [u8; 16], // All zeros creates a new chord
+    pub name: String,
+    pub keys: Vec<String>,    // Key names, at least two
+    pub outputs: Vec<String>, // Gamepad control names
+    #[serde(default)]
+    pub suppress_keys: bool,
+    #[serde(default)]
+    pub created_at: i64,
+}
+
+impl From<ChordDto> for ChordInfo {
+    fn from(dto: ChordDto) -> Self {
+        Self {
+            id: dto.id,
+            name: dto.name,
+            keys: dto.keys,
+            outputs: dto.outputs,
+            suppress_keys: dto.suppress_keys,
+            created_at: dto.created_at as i64,
+        }
+    }
+}
+
+impl From<ChordInfo> for ChordDto {
+    fn from(info: ChordInfo) -> Self {
+        Self {
+            id: info.id,
+            name: info.name,
+            keys: info.keys,
+            outputs: info.outputs,
+            suppress_keys: info.suppress_keys,
             created_at: info.created_at as u64,
         }
     }
@@ -0,0 +1,89 @@
+use crate::profile::profiles::default_acceleration_max_boost;
+
+/// Compiled camera-style acceleration of a stick mapping.
+///
+/// Once a key has been held for `onset_secs`, its output gain ramps linearly
+/// to `1 + max_boost` over `ramp_secs`. The gain scales the curve output, so
+/// deeper presses accelerate to higher speeds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Acceleration {
+    pub onset_secs: f32,
+    pub ramp_secs: f32,
+    pub max_boost: f32, // Extra gain at the end of the ramp
+}
+
+impl Acceleration {
+    pub fn new(onset_ms: u32, ramp_ms: u32, max_boost: f32) -> Self {
+        let max_boost = if max_boost.is_finite() && max_boost >= 0.0 {
+            max_boost
+        } else {
+            default_acceleration_max_boost()
+        };
+
+        Self {
+            onset_secs: onset_ms as f32 / 1000.0,
+            ramp_secs: ramp_ms as f32 / 1000.0,
+            max_boost,
+        }
+    }
+
+    /// Output gain after the key has been held for `held_secs`.
+    #[inline(always)]
+    pub fn gain(&self, held_secs: f32) -> f32 {
+        let ramped = held_secs - self.onset_secs;
+        let progress = if ramped < 0.0 {
+            0.0
+        } else if self.ramp_secs > 0.0 {
+            (ramped / self.ramp_secs).min(1.0)
+        } else {
+            1.0
+        };
+        1.0 + self.max_boost * progress
+    }
+}
+
+/// Hold timer of one key output, carried between frames.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AccelerationState {
+    held_secs: f32,
+}
+
+impl AccelerationState {
+    /// Accelerate a curve output in [0.0, 1.0] held for another `delta_secs`.
+    ///
+    /// The timer restarts whenever the output drops to zero.
+    #[inline]
+    pub fn apply(&mut self, acceleration: Acceleration, value: f32, delta_secs: f32) -> f32 {
+        if value <= 0.0 {
+            self.held_secs = 0.0;
+            return value;
+        }
+
+        let gain = acceleration.gain(self.held_secs);
+        self.held_secs += delta_secs;
+        (value * gain).min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ramps_after_onset() {
+        let acceleration = Acceleration::new(100, 200, 1.0);
+        let mut state = AccelerationState::default();
+
+        assert_eq!(state.apply(acceleration, 0.25, 0.1), 0.25);
+        assert_eq!(state.apply(acceleration, 0.25, 0.1), 0.25);
+        assert_eq!(state.apply(acceleration, 0.25, 0.1), 0.375);
+        assert_eq!(state.apply(acceleration, 0.25, 0.1), 0.5);
+        assert_eq!(state.apply(acceleration, 0.25, 0.1), 0.5);
+        // Saturates at full deflection.
+        assert_eq!(state.apply(acceleration, 0.75, 0.1), 1.0);
+
+        // Releasing restarts the timer.
+        assert_eq!(state.apply(acceleration, 0.0, 0.1), 0.0);
+        assert_eq!(state.apply(acceleration, 0.25, 0.1), 0.25);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::profile::profiles::ButtonBehavior;
+use std::time::{Duration, Instant};
+
+/// Compiled button behaviour of a mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ButtonMode {
+    #[default]
+    Normal,
+    /// Alternates pressed/released every half `period` while held.
+    Turbo { period: Duration },
+    /// Each press flips the latched state.
+    Toggle,
+}
+
+impl ButtonMode {
+    pub fn new(behavior: ButtonBehavior, turbo_rate_hz: f32) -> Self {
+        match behavior {
+            ButtonBehavior::Normal => ButtonMode::Normal,
+            ButtonBehavior::Toggle => ButtonMode::Toggle,
+            ButtonBehavior::Turbo => {
+                let rate = if turbo_rate_hz.is_finite() && turbo_rate_hz > 0.0 {
+                    turbo_rate_hz.min(MAX_TURBO_RATE_HZ)
+                } else {
+                    crate::profile::profiles::default_turbo_rate()
+                };
+                ButtonMode::Turbo {
+                    period: Duration::from_secs_f32(1.0 / rate),
+                }
+            }
+        }
+    }
+}
+
+/// Highest turbo rate the 120 FPS mapping loop can render (one frame on, one frame off).
+pub const MAX_TURBO_RATE_HZ: f32 = 60.0;
+
+/// Runtime state of a button output, carried between frames.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ButtonModeState {
+    held_since: Option<Instant>,
+    latched: bool,
+}
+
+impl ButtonModeState {
+    /// Return the button output for the current physical `pressed` state.
+    pub fn update(&mut self, mode: ButtonMode, pressed: bool, now: Instant) -> bool {
+        let was_pressed = self.held_since.is_some();
+        if !pressed {
+            self.held_since = None;
+        } else if !was_pressed {
+            self.held_since = Some(now);
+        }
+
+        match mode {
+            ButtonMode::Normal => pressed,
+            ButtonMode::Toggle => {
+                if pressed && !was_pressed {
+                    self.latched = !self.latched;
+                }
+                self.latched
+            }
+            ButtonMode::Turbo { period } => match self.held_since {
+                Some(since) => {
+                    let half_period = (period / 2).as_nanos().max(1);
+                    let phase = now.saturating_duration_since(since).as_nanos() / half_period;
+                    phase.is_multiple_of(2)
+                }
+                None => false,
+            },
+        }
+    }
+
+    /// Whether a toggle output is latched on.
+    pub fn is_latched(&self) -> bool {
+        self.latched
+    }
+
+    /// Drop a toggle latch, keeping the physical press.
+    pub fn unlatch(&mut self) {
+        self.latched = false;
+    }
+
+    /// Forget the physical press, keeping a toggle latch.
+    pub fn release(&mut self) {
+        self.held_since = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggle_latches_on_press() {
+        let mode = ButtonMode::new(ButtonBehavior::Toggle, 0.0);
+        let now = Instant::now();
+        let mut state = ButtonModeState::default();
+
+        assert!(state.update(mode, true, now));
+        assert!(state.update(mode, false, now));
+        assert!(state.update(mode, false, now));
+        assert!(!state.update(mode, true, now));
+        assert!(!state.update(mode, false, now));
+    }
+
+    #[test]
+    fn turbo_alternates_while_held() {
+        let mode = ButtonMode::new(ButtonBehavior::Turbo, 10.0);
+        let start = Instant::now();
+        let mut state = ButtonModeState::default();
+
+        assert!(state.update(mode, true, start));
+        assert!(state.update(mode, true, start + Duration::from_millis(40)));
+        assert!(!state.update(mode, true, start + Duration::from_millis(60)));
+        assert!(state.update(mode, true, start + Duration::from_millis(110)));
+        assert!(!state.update(mode, false, start + Duration::from_millis(120)));
+    }
+}
@@ -0,0 +1,33 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Chords a sub-profile can hold, one bit each in the active set.
+pub const MAX_CHORDS: usize = 64;
+
+/// Chords held on the keyboard, indexed like `CompiledProfile::chords`.
+///
+/// The event manager decides when a chord is held, on the OS key events of its
+/// keys; the mapping loop reads the set to drive analog outputs and suppression.
+static ACTIVE_CHORDS: AtomicU64 = AtomicU64::new(0);
+
+/// Mark a chord of the active sub-profile as held or released.
+pub fn set_chord_active(index: usize, active: bool) {
+    if index >= MAX_CHORDS {
+        return;
+    }
+    if active {
+        ACTIVE_CHORDS.fetch_or(1 << index, Ordering::Release);
+    } else {
+        ACTIVE_CHORDS.fetch_and(!(1 << index), Ordering::Release);
+    }
+}
+
+/// Release all chords, e.g. after a sub-profile switch.
+pub fn clear_active_chords() {
+    ACTIVE_CHORDS.store(0, Ordering::Release);
+}
+
+/// Whether a chord of the active sub-profile is held.
+#[inline(always)]
+pub fn is_chord_active(index: usize) -> bool {
+    index < MAX_CHORDS && ACTIVE_CHORDS.load(Ordering::Acquire) & (1 << index) != 0
+}
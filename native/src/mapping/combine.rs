@@ -0,0 +1,84 @@
+use crate::profile::profiles::CombineMode;
+
+/// Collects the processed values of every key driving one analog control in a frame.
+#[derive(Debug, Clone, Copy)]
+pub struct ControlAccumulator {
+    max: f32,
+    sum: f32,
+    active: u32,
+    // (priority, value) of the highest priority active key.
+    first: Option<(u16, f32)>,
+}
+
+impl ControlAccumulator {
+    pub const EMPTY: Self = Self {
+        max: 0.0,
+        sum: 0.0,
+        active: 0,
+        first: None,
+    };
+
+    /// Add a key value. Keys at rest (0.0) do not count as active.
+    #[inline(always)]
+    pub fn add(&mut self, value: f32, priority: u16) {
+        if value <= 0.0 {
+            return;
+        }
+
+        self.max = self.max.max(value);
+        self.sum += value;
+        self.active += 1;
+
+        if self.first.is_none_or(|(best, _)| priority < best) {
+            self.first = Some((priority, value));
+        }
+    }
+
+    /// Combined control value in [0.0, 1.0].
+    #[inline(always)]
+    pub fn combine(&self, mode: CombineMode) -> f32 {
+        if self.active == 0 {
+            return 0.0;
+        }
+
+        match mode {
+            CombineMode::Max => self.max,
+            CombineMode::SumClamped => self.sum.min(1.0),
+            CombineMode::Average => self.sum / self.active as f32,
+            CombineMode::Priority => self.first.map_or(0.0, |(_, value)| value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combine_modes() {
+        let mut accumulator = ControlAccumulator::EMPTY;
+        accumulator.add(0.3, 2);
+        accumulator.add(0.0, 0);
+        accumulator.add(0.5, 1);
+
+        assert_eq!(accumulator.combine(CombineMode::Max), 0.5);
+        assert_eq!(accumulator.combine(CombineMode::SumClamped), 0.8);
+        assert_eq!(accumulator.combine(CombineMode::Average), 0.4);
+        assert_eq!(accumulator.combine(CombineMode::Priority), 0.5);
+
+        accumulator.add(0.6, 3);
+        assert_eq!(accumulator.combine(CombineMode::SumClamped), 1.0);
+    }
+
+    #[test]
+    fn empty_control_is_zero() {
+        for mode in [
+            CombineMode::Max,
+            CombineMode::SumClamped,
+            CombineMode::Average,
+            CombineMode::Priority,
+        ] {
+            assert_eq!(ControlAccumulator::EMPTY.combine(mode), 0.0);
+        }
+    }
+}
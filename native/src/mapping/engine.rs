@@ -1,4 +1,13 @@
-use crate::profile::profiles::GamepadControl;
+use crate::api::types::AnalogInput;
+use crate::curves::StickMagnitudeModifier;
+use crate::gamepad::AtomicGamepadState;
+use crate::mapping::acceleration::AccelerationState;
+use crate::mapping::chord::is_chord_active;
+use crate::mapping::combine::ControlAccumulator;
+use crate::mapping::macros::{MacroOutput, MacroScheduler};
+use crate::mapping::socd::StickAxis;
+use crate::mapping::state::MappingState;
+use crate::profile::profiles::{CompiledProfile, GamepadControl, OutputAxis, ANALOG_CONTROL_COUNT};
 use arc_swap::ArcSwap;
 use log::{debug, error};
 use std::sync::{
@@ -8,6 +17,15 @@ use std::sync::{
 use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 
+/// Gamepad outputs computed by one mapping frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct FrameOutput {
+    left_stick: (f32, f32),
+    right_stick: (f32, f32),
+    triggers: (f32, f32),
+    buttons: u16, // XButtons held by analog actuation, toggles and macros
+}
+
 pub struct MappingEngine {
     mapping_active: Arc<AtomicBool>,
     mapping_thread: Arc<Mutex<Option<JoinHandle<()>>>>,
@@ -213,11 +231,19 @@ impl MappingEngine {
         const TARGET_FPS: u64 = 120; // Target loop rate in Hz.
         const FRAME_TIME: Duration = Duration::from_micros(1_000_000 / TARGET_FPS);
         const WARN_TARGET_MICROS: u64 = 8333; // 8.33ms warn target (120 FPS)
+        const MAX_FRAME_DELTA: Duration = Duration::from_millis(100); // Caps time-based state after stalls
 
         // Pre-allocate input buffer.
         let mut input_buffer = Vec::with_capacity(256); // Max possible keys, allocated once.
 
-        let mut _last_frame = Instant::now(); // Track for potential future use
+        // Per-key state carried between frames (hysteresis, SOCD press order, analog buttons).
+        let mut mapping_state = MappingState::new();
+
+        // Macros run on the loop clock; requests queued while stopped are dropped.
+        let mut macros = MacroScheduler::new();
+        macros.discard_requests();
+
+        let mut last_frame = Instant::now();
         #[cfg(debug_assertions)]
         let mut last_log_time = Instant::now();
         #[cfg(debug_assertions)]
@@ -227,6 +253,13 @@ impl MappingEngine {
             let frame_start = Instant::now();
             frame_count.fetch_add(1, Ordering::Relaxed);
 
+            // Real time since the previous frame, for rate-based state (steering, smoothing, slew).
+            let frame_delta = frame_start
+                .duration_since(last_frame)
+                .min(MAX_FRAME_DELTA)
+                .as_secs_f32();
+            last_frame = frame_start;
+
             // Read inputs while reusing the pre-allocated buffer.
             let input_success = {
                 let wooting_guard = wooting_sdk.as_ref().lock().unwrap();
@@ -238,100 +271,53 @@ impl MappingEngine {
                 }
             };
 
-            if input_success && !input_buffer.is_empty() {
-                let profile_guard = current_profile.load();
-
-                if let Some(ref profile) = profile_guard.as_ref() {
-                    // Reset per-frame analog outputs.
-                    let mut left_trigger_val: f64 = 0.0;
-                    let mut right_trigger_val: f64 = 0.0;
-
-                    // Process each input through active mappings.
-                    let mut left_x_positive = 0.0f32;
-                    let mut left_x_negative = 0.0f32;
-                    let mut left_y_positive = 0.0f32;
-                    let mut left_y_negative = 0.0f32;
-                    let mut right_x_positive = 0.0f32;
-                    let mut right_x_negative = 0.0f32;
-                    let mut right_y_positive = 0.0f32;
-                    let mut right_y_negative = 0.0f32;
-
-                    // Only analog inputs are processed here; digital buttons are handled by the event manager.
-                    for input in &input_buffer {
-                        if let Some(compiled_mapping) =
-                            profile.mappings.get(&(input.key_code as u16))
-                        {
-                            // Skip digital button mappings in this loop.
-                            let is_analog_control = matches!(
-                                compiled_mapping.gamepad_control,
-                                GamepadControl::LeftStickUp
-                                    | GamepadControl::LeftStickDown
-                                    | GamepadControl::LeftStickLeft
-                                    | GamepadControl::LeftStickRight
-                                    | GamepadControl::RightStickUp
-                                    | GamepadControl::RightStickDown
-                                    | GamepadControl::RightStickLeft
-                                    | GamepadControl::RightStickRight
-                                    | GamepadControl::LeftTrigger
-                                    | GamepadControl::RightTrigger
-                            );
-
-                            if is_analog_control {
-                                mapping_hits.fetch_add(1, Ordering::Relaxed);
-
-                                let processed_value =
-                                    compiled_mapping.process_input(input.analog_value as f32);
-
-                                match compiled_mapping.gamepad_control {
-                                    GamepadControl::LeftStickUp => {
-                                        left_y_positive = processed_value.max(left_y_positive)
-                                    }
-                                    GamepadControl::LeftStickDown => {
-                                        left_y_negative = processed_value.max(left_y_negative)
-                                    }
-                                    GamepadControl::LeftStickLeft => {
-                                        left_x_negative = processed_value.max(left_x_negative)
-                                    }
-                                    GamepadControl::LeftStickRight => {
-                                        left_x_positive = processed_value.max(left_x_positive)
-                                    }
-
-                                    GamepadControl::RightStickUp => {
-                                        right_y_positive = processed_value.max(right_y_positive)
-                                    }
-                                    GamepadControl::RightStickDown => {
-                                        right_y_negative = processed_value.max(right_y_negative)
-                                    }
-                                    GamepadControl::RightStickLeft => {
-                                        right_x_negative = processed_value.max(right_x_negative)
-                                    }
-                                    GamepadControl::RightStickRight => {
-                                        right_x_positive = processed_value.max(right_x_positive)
-                                    }
-
-                                    GamepadControl::LeftTrigger => {
-                                        left_trigger_val =
-                                            (processed_value as f64).max(left_trigger_val)
-                                    }
-                                    GamepadControl::RightTrigger => {
-                                        right_trigger_val =
-                                            (processed_value as f64).max(right_trigger_val)
-                                    }
-
-                                    _ => {}
-                                }
-                            }
-                        } else {
-                            mapping_misses.fetch_add(1, Ordering::Relaxed);
-                        }
-                    }
+            if input_success {
+                mapping_state.release_missing(&input_buffer);
+            } else {
+                input_buffer.clear();
+            }
+
+            let profile_guard = current_profile.load();
+            let active_profile = profile_guard.as_ref().as_ref();
+
+            // Start requested macros and advance running ones (cancelled on profile change).
+            let macros_active = macros.update(active_profile, frame_start);
+
+            // Some outputs keep moving after every key is released.
+            let settling = mapping_state.is_settling(active_profile.map(Arc::as_ref));
+
+            if (input_success && !input_buffer.is_empty()) || macros_active || settling {
+                if let Some(profile) = active_profile {
+                    mapping_state
+                        .sync_profile(profile, crate::ATOMIC_GAMEPAD_STATE.button_resets());
+
+                    let output = Self::map_frame(
+                        profile,
+                        &mut mapping_state,
+                        macros.output(),
+                        &input_buffer,
+                        frame_start,
+                        frame_delta,
+                        &mapping_hits,
+                        &mapping_misses,
+                    );
 
                     use crate::ATOMIC_GAMEPAD_STATE;
 
-                    let left_stick_x = (left_x_positive - left_x_negative).clamp(-1.0, 1.0);
-                    let left_stick_y = (left_y_positive - left_y_negative).clamp(-1.0, 1.0);
-                    let right_stick_x = (right_x_positive - right_x_negative).clamp(-1.0, 1.0);
-                    let right_stick_y = (right_y_positive - right_y_negative).clamp(-1.0, 1.0);
+                    // Apply analog-actuated button transitions, including latched toggles
+                    // whose keys are up.
+                    let (pressed_buttons, released_buttons) =
+                        mapping_state.update_analog_buttons(output.buttons);
+                    if released_buttons != 0 {
+                        ATOMIC_GAMEPAD_STATE.release_buttons(released_buttons);
+                    }
+                    if pressed_buttons != 0 {
+                        ATOMIC_GAMEPAD_STATE.press_buttons(pressed_buttons);
+                    }
+
+                    let (left_stick_x, left_stick_y) = output.left_stick;
+                    let (right_stick_x, right_stick_y) = output.right_stick;
+                    let (left_trigger_val, right_trigger_val) = output.triggers;
 
                     ATOMIC_GAMEPAD_STATE.set_sticks(
                         left_stick_x as f64,
@@ -339,7 +325,8 @@ impl MappingEngine {
                         right_stick_x as f64,
                         right_stick_y as f64,
                     );
-                    ATOMIC_GAMEPAD_STATE.set_triggers(left_trigger_val, right_trigger_val);
+                    ATOMIC_GAMEPAD_STATE
+                        .set_triggers(left_trigger_val as f64, right_trigger_val as f64);
 
                     // Create unified ViGEm report from atomic state (includes digital buttons from events)
                     let vigem_gamepad = ATOMIC_GAMEPAD_STATE.to_vigem_gamepad();
@@ -351,6 +338,10 @@ impl MappingEngine {
                             error!("[ENGINE] ViGEm update failed: {}", e);
                         }
                     }
+                } else {
+                    // Nothing drives the outputs without a profile.
+                    mapping_state.steering.reset();
+                    mapping_state.slew.reset();
                 }
             }
 
@@ -402,11 +393,427 @@ impl MappingEngine {
                     last_logged_frames_over_budget = current_frames_over_budget;
                 }
             }
+        }
 
-            _last_frame = frame_start;
+        // Cancel macros and release buttons still held by analog actuation or macros.
+        macros.cancel();
+        macros.discard_requests();
+        let (_, released_buttons) = mapping_state.update_analog_buttons(0);
+        if released_buttons != 0 {
+            crate::ATOMIC_GAMEPAD_STATE.release_buttons(released_buttons);
         }
 
         #[cfg(debug_assertions)]
         debug!("[INFO] Mapping loop stopped");
     }
+
+    /// Map one frame of key inputs to gamepad outputs, advancing the per-key state.
+    #[allow(clippy::too_many_arguments)]
+    fn map_frame(
+        profile: &CompiledProfile,
+        mapping_state: &mut MappingState,
+        macro_output: &MacroOutput,
+        input_buffer: &[AnalogInput],
+        frame_start: Instant,
+        frame_delta: f32,
+        mapping_hits: &AtomicU64,
+        mapping_misses: &AtomicU64,
+    ) -> FrameOutput {
+        // Reset per-frame analog outputs.
+        let mut accumulators = [ControlAccumulator::EMPTY; ANALOG_CONTROL_COUNT];
+        let mut analog_buttons: u16 = 0;
+        let mut angle_vectors = [(0.0f32, 0.0f32); 2]; // Left, right stick
+
+        // Chords held per the event manager drive their analog outputs at the depth
+        // of their shallowest key; chord buttons are handled by the event manager.
+        let mut suppressed_keys = std::mem::take(&mut mapping_state.suppressed_keys);
+        suppressed_keys.clear();
+        for (index, chord) in profile.chords.iter().enumerate() {
+            if !is_chord_active(index) {
+                continue;
+            }
+            let depth = chord.key_codes.iter().fold(1.0f32, |depth, variants| {
+                let value = input_buffer
+                    .iter()
+                    .filter(|input| variants.contains(&(input.key_code as u16)))
+                    .fold(0.0f32, |value, input| value.max(input.analog_value as f32));
+                depth.min(value)
+            });
+            if chord.suppress_keys {
+                suppressed_keys.extend(chord.key_codes.iter().flatten());
+            }
+            for control in &chord.outputs {
+                if let Some(index) = control.analog_index() {
+                    accumulators[index].add(depth, 0);
+                }
+            }
+        }
+
+        // Digital buttons are handled by the event manager unless they use analog actuation.
+        for input in input_buffer {
+            if suppressed_keys.contains(&(input.key_code as u16)) {
+                continue;
+            }
+            if let Some(outputs) = profile.mappings.get(&(input.key_code as u16)) {
+                for (output, compiled_mapping) in outputs.iter().enumerate() {
+                    // Angle mappings add a vector to their stick.
+                    if let Some(direction) = compiled_mapping.direction {
+                        mapping_hits.fetch_add(1, Ordering::Relaxed);
+
+                        let key_state = mapping_state.key(input.key_code as u16, output);
+                        let raw_value = key_state.smoothing.filter(
+                            compiled_mapping.smoothing,
+                            input.analog_value as f32,
+                            frame_delta,
+                        );
+                        let processed_value = compiled_mapping.analog_output(
+                            raw_value,
+                            &mut key_state.engaged,
+                            &mut key_state.acceleration,
+                            frame_delta,
+                        );
+                        let vector = &mut angle_vectors[direction.right_stick as usize];
+                        vector.0 += processed_value * direction.x;
+                        vector.1 += processed_value * direction.y;
+                        continue;
+                    }
+
+                    let Some(index) = compiled_mapping.gamepad_control.analog_index() else {
+                        let Some(actuation) = compiled_mapping.actuation else {
+                            continue;
+                        };
+                        mapping_hits.fetch_add(1, Ordering::Relaxed);
+
+                        let key_state = mapping_state.key(input.key_code as u16, output);
+                        key_state.pressed = actuation.update(
+                            input.analog_value as f32,
+                            key_state.pressed,
+                            &mut key_state.travel_extreme,
+                        );
+                        // Turbo and toggle run on the loop clock.
+                        let output = key_state.button.update(
+                            compiled_mapping.button_mode,
+                            key_state.pressed,
+                            frame_start,
+                        );
+                        if let Some(button) = AtomicGamepadState::gamepad_control_to_xbox_button(
+                            &compiled_mapping.gamepad_control,
+                        ) {
+                            key_state.button_mask = button as u16;
+                            if output {
+                                analog_buttons |= button as u16;
+                            }
+                        }
+                        continue;
+                    };
+                    mapping_hits.fetch_add(1, Ordering::Relaxed);
+
+                    let key_state = mapping_state.key(input.key_code as u16, output);
+                    // Smooth sensor jitter before the curve.
+                    let raw_value = key_state.smoothing.filter(
+                        compiled_mapping.smoothing,
+                        input.analog_value as f32,
+                        frame_delta,
+                    );
+                    // Curve, acceleration and inversion.
+                    let processed_value = compiled_mapping.analog_output(
+                        raw_value,
+                        &mut key_state.engaged,
+                        &mut key_state.acceleration,
+                        frame_delta,
+                    );
+
+                    accumulators[index].add(processed_value, compiled_mapping.priority);
+                }
+            } else {
+                mapping_misses.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        mapping_state.suppressed_keys = suppressed_keys;
+
+        // Inverted outputs of released keys rest at their full value.
+        for &(key_code, output) in &profile.resting_outputs {
+            if input_buffer
+                .iter()
+                .any(|input| input.key_code as u16 == key_code)
+            {
+                continue;
+            }
+            let compiled_mapping = &profile.mappings[&key_code][output];
+            let resting_value = compiled_mapping.analog_output(
+                0.0,
+                &mut false,
+                &mut AccelerationState::default(),
+                frame_delta,
+            );
+            if let Some(direction) = compiled_mapping.direction {
+                let vector = &mut angle_vectors[direction.right_stick as usize];
+                vector.0 += resting_value * direction.x;
+                vector.1 += resting_value * direction.y;
+            } else if let Some(index) = compiled_mapping.gamepad_control.analog_index() {
+                accumulators[index].add(resting_value, compiled_mapping.priority);
+            }
+        }
+
+        // Macro outputs join the keys with the lowest priority.
+        for (accumulator, value) in accumulators.iter_mut().zip(macro_output.analog) {
+            accumulator.add(value, u16::MAX);
+        }
+        analog_buttons |= macro_output.buttons;
+
+        // Combine keys sharing a control (max, sum, average, priority).
+        let combined = |control: GamepadControl| {
+            control.analog_index().map_or(0.0, |index| {
+                accumulators[index].combine(profile.combine_modes[index])
+            })
+        };
+
+        let left_y_positive = combined(GamepadControl::LeftStickUp);
+        let left_y_negative = combined(GamepadControl::LeftStickDown);
+        let left_x_negative = combined(GamepadControl::LeftStickLeft);
+        let left_x_positive = combined(GamepadControl::LeftStickRight);
+        let right_y_positive = combined(GamepadControl::RightStickUp);
+        let right_y_negative = combined(GamepadControl::RightStickDown);
+        let right_x_negative = combined(GamepadControl::RightStickLeft);
+        let right_x_positive = combined(GamepadControl::RightStickRight);
+        let left_trigger_val = combined(GamepadControl::LeftTrigger);
+        let right_trigger_val = combined(GamepadControl::RightTrigger);
+
+        // Resolve opposing directions per axis (SOCD policy).
+        let socd = &mut mapping_state.socd;
+        let left_stick_x = socd.resolve(
+            profile.socd,
+            StickAxis::LeftX,
+            left_x_positive,
+            left_x_negative,
+        );
+        let left_stick_y = socd.resolve(
+            profile.socd,
+            StickAxis::LeftY,
+            left_y_positive,
+            left_y_negative,
+        );
+        let right_stick_x = socd.resolve(
+            profile.socd,
+            StickAxis::RightX,
+            right_x_positive,
+            right_x_negative,
+        );
+        let right_stick_y = socd.resolve(
+            profile.socd,
+            StickAxis::RightY,
+            right_y_positive,
+            right_y_negative,
+        );
+
+        // Steering axes integrate the resolved value into a wheel position.
+        let steering = &mut mapping_state.steering;
+        let mut steer = |axis: StickAxis, value: f32| match profile.steering[axis.index()] {
+            Some(rates) => steering.update(rates, axis, value, frame_delta),
+            None => value,
+        };
+        let left_stick_x = steer(StickAxis::LeftX, left_stick_x);
+        let left_stick_y = steer(StickAxis::LeftY, left_stick_y);
+        let right_stick_x = steer(StickAxis::RightX, right_stick_x);
+        let right_stick_y = steer(StickAxis::RightY, right_stick_y);
+
+        // Add angle mapping vectors before shaping clamps the sticks.
+        let [(left_angle_x, left_angle_y), (right_angle_x, right_angle_y)] = angle_vectors;
+
+        // Shape combined axes (radial dead zone, circular output).
+        let (left_stick_x, left_stick_y) = profile
+            .stick
+            .apply(left_stick_x + left_angle_x, left_stick_y + left_angle_y);
+        let (right_stick_x, right_stick_y) = profile
+            .stick
+            .apply(right_stick_x + right_angle_x, right_stick_y + right_angle_y);
+
+        // Scale stick magnitude while modifier keys are held (walk/sprint).
+        let (left_scale, right_scale) = StickMagnitudeModifier::stick_scales(
+            &profile.stick_modifiers,
+            input_buffer
+                .iter()
+                .map(|input| (input.key_code as u16, input.analog_value as f32)),
+        );
+        let (left_stick_x, left_stick_y) = profile
+            .stick
+            .scale((left_stick_x, left_stick_y), left_scale);
+        let (right_stick_x, right_stick_y) = profile
+            .stick
+            .scale((right_stick_x, right_stick_y), right_scale);
+
+        // Route sticks to the outputs (swap, invert Y).
+        let ((left_stick_x, left_stick_y), (right_stick_x, right_stick_y)) = profile
+            .layout
+            .apply((left_stick_x, left_stick_y), (right_stick_x, right_stick_y));
+
+        // Limit how fast each output axis may change.
+        let slew = &mut mapping_state.slew;
+        let mut limit = |axis: OutputAxis, value: f32| {
+            slew.limit(axis, profile.slew_rates[axis.index()], value, frame_delta)
+        };
+        let left_stick_x = limit(OutputAxis::LeftStickX, left_stick_x);
+        let left_stick_y = limit(OutputAxis::LeftStickY, left_stick_y);
+        let right_stick_x = limit(OutputAxis::RightStickX, right_stick_x);
+        let right_stick_y = limit(OutputAxis::RightStickY, right_stick_y);
+        let left_trigger_val = limit(OutputAxis::LeftTrigger, left_trigger_val);
+        let right_trigger_val = limit(OutputAxis::RightTrigger, right_trigger_val);
+
+        FrameOutput {
+            left_stick: (left_stick_x, left_stick_y),
+            right_stick: (right_stick_x, right_stick_y),
+            triggers: (left_trigger_val, right_trigger_val),
+            // Toggles stay latched while their keys are up.
+            buttons: analog_buttons | mapping_state.latched_buttons(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conversions::vk;
+    use crate::mapping::chord::{clear_active_chords, set_chord_active};
+    use crate::profile::profiles::{
+        ChordMapping, GameProfile, KeyMapping, ResponseCurve, SmoothingFilter,
+    };
+
+    const FRAME: f32 = 1.0 / 120.0;
+
+    fn compile(mappings: Vec<KeyMapping>) -> Arc<CompiledProfile> {
+        let mut profile = GameProfile::new("Test".to_string());
+        profile.sub_profiles[0].mappings = mappings;
+        let sub_profile_name = profile.sub_profiles[0].name.clone();
+        Arc::new(profile.compile_profile(&sub_profile_name).unwrap())
+    }
+
+    /// Run frames of (key code, depth) inputs like the mapping loop does and
+    /// return the outputs left on the gamepad.
+    fn run_frames(profile: &Arc<CompiledProfile>, frames: &[&[(u16, f32)]]) -> FrameOutput {
+        let mut mapping_state = MappingState::new();
+        let (hits, misses) = (AtomicU64::new(0), AtomicU64::new(0));
+        let start = Instant::now();
+        let mut gamepad = FrameOutput::default();
+
+        for (frame, keys) in frames.iter().enumerate() {
+            let inputs: Vec<AnalogInput> = keys
+                .iter()
+                .map(|&(key_code, depth)| AnalogInput {
+                    key_code: key_code as i32,
+                    analog_value: depth as f64,
+                })
+                .collect();
+            mapping_state.release_missing(&inputs);
+            if inputs.is_empty() && !mapping_state.is_settling(Some(profile)) {
+                continue;
+            }
+
+            mapping_state.sync_profile(profile, 0);
+            gamepad = MappingEngine::map_frame(
+                profile,
+                &mut mapping_state,
+                &MacroOutput::default(),
+                &inputs,
+                start + Duration::from_secs_f32(FRAME * frame as f32),
+                FRAME,
+                &hits,
+                &misses,
+            );
+        }
+        gamepad
+    }
+
+    #[test]
+    fn smoothed_key_release_centres_stick() {
+        let profile = compile(vec![KeyMapping {
+            key_name: "W".to_string(),
+            gamepad_control: GamepadControl::LeftStickUp,
+            smoothing: SmoothingFilter::Ema,
+            ..KeyMapping::default()
+        }]);
+        let held: &[(u16, f32)] = &[(vk::W, 1.0)];
+
+        let pressed = run_frames(&profile, &[held; 10]);
+        assert!(pressed.left_stick.1 > 0.9);
+
+        // The zero-depth release report centres the stick by itself...
+        let released: &[(u16, f32)] = &[(vk::W, 0.0)];
+        assert_eq!(
+            run_frames(&profile, &[held, held, released]).left_stick,
+            (0.0, 0.0)
+        );
+        // ...and idle frames without the key keep it there.
+        let idle = run_frames(&profile, &[held, held, released, &[], &[]]);
+        assert_eq!(idle.left_stick, (0.0, 0.0));
+    }
+
+    #[test]
+    fn hysteresis_band_keeps_dead_zone_normalisation() {
+        let profile = compile(vec![KeyMapping {
+            key_name: "W".to_string(),
+            gamepad_control: GamepadControl::LeftTrigger,
+            response_curve: ResponseCurve::Linear,
+            dead_zone_inner: 0.1,
+            dead_zone_outer: 0.9,
+            dead_zone_hysteresis: 0.04,
+            output_min: 0.2,
+            ..KeyMapping::default()
+        }]);
+        let trigger = |frames: &[&[(u16, f32)]]| run_frames(&profile, frames).triggers.0;
+
+        // Depths past the inner dead zone map as without hysteresis...
+        assert!((trigger(&[&[(vk::W, 0.5)]]) - 0.6).abs() < 1e-6);
+        // ...and an engaged key holds the output minimum inside the release band.
+        assert_eq!(trigger(&[&[(vk::W, 0.5)], &[(vk::W, 0.08)]]), 0.2);
+        assert_eq!(trigger(&[&[(vk::W, 0.5)], &[(vk::W, 0.05)]]), 0.0);
+        assert_eq!(trigger(&[&[(vk::W, 0.08)]]), 0.0);
+    }
+
+    #[test]
+    fn engaged_key_stays_active_in_release_band() {
+        let profile = compile(vec![KeyMapping {
+            key_name: "W".to_string(),
+            gamepad_control: GamepadControl::LeftTrigger,
+            dead_zone_inner: 0.1,
+            dead_zone_outer: 0.9,
+            dead_zone_hysteresis: 0.04,
+            ..KeyMapping::default()
+        }]);
+        let trigger = |frames: &[&[(u16, f32)]]| run_frames(&profile, frames).triggers.0;
+        let engaged: &[(u16, f32)] = &[(vk::W, 0.1)];
+
+        // Jitter between release and engage keeps an engaged key above 0...
+        for depth in [0.1, 0.09, 0.07, 0.0601] {
+            assert!(trigger(&[engaged, &[(vk::W, depth)]]) > 0.0);
+        }
+        // ...until it drops to the release threshold, while an idle key stays off.
+        assert_eq!(trigger(&[engaged, &[(vk::W, 0.05)]]), 0.0);
+        assert_eq!(trigger(&[&[(vk::W, 0.09)]]), 0.0);
+    }
+
+    #[test]
+    fn chord_follows_event_activation() {
+        let mut profile = GameProfile::new("Test".to_string());
+        profile.sub_profiles[0].chords = vec![ChordMapping {
+            id: uuid::Uuid::nil(),
+            name: "Dodge".to_string(),
+            keys: vec!["Shift".to_string(), "W".to_string()],
+            outputs: vec![GamepadControl::LeftTrigger],
+            suppress_keys: false,
+            created_at: 0,
+            modified_at: 0,
+        }];
+        let sub_profile_name = profile.sub_profiles[0].name.clone();
+        let profile = Arc::new(profile.compile_profile(&sub_profile_name).unwrap());
+        let held: &[(u16, f32)] = &[(vk::LSHIFT, 0.6), (vk::W, 0.8)];
+
+        // Pressed keys alone do not hold the chord until the event path does.
+        assert_eq!(run_frames(&profile, &[held]).triggers.0, 0.0);
+
+        set_chord_active(0, true);
+        let triggers = run_frames(&profile, &[held]).triggers;
+        clear_active_chords();
+        assert!((triggers.0 - 0.6).abs() < 1e-6);
+    }
 }
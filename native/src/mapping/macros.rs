@@ -0,0 +1,215 @@
+use crate::gamepad::AtomicGamepadState;
+use crate::profile::profiles::{
+    CompiledProfile, GamepadControl, GamepadMacro, MacroStep, ANALOG_CONTROL_COUNT,
+};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Macro start requests from the input thread, drained by the mapping loop.
+static MACRO_REQUESTS: Mutex<Vec<Uuid>> = Mutex::new(Vec::new());
+
+/// Queue a macro of the active sub-profile to start on the next mapping frame.
+pub fn request_macro(macro_id: Uuid) {
+    MACRO_REQUESTS.lock().unwrap().push(macro_id);
+}
+
+fn take_macro_requests() -> Vec<Uuid> {
+    std::mem::take(&mut *MACRO_REQUESTS.lock().unwrap())
+}
+
+/// Gamepad outputs driven by a macro.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MacroOutput {
+    pub buttons: u16,                        // XButtons bitmask
+    pub analog: [f32; ANALOG_CONTROL_COUNT], // Indexed by `analog_index`
+}
+
+impl MacroOutput {
+    fn set(&mut self, control: GamepadControl, value: f32) {
+        let value = if value.is_finite() { value } else { 0.0 };
+        if let Some(index) = control.analog_index() {
+            self.analog[index] = value.clamp(0.0, 1.0);
+        } else if let Some(button) = AtomicGamepadState::gamepad_control_to_xbox_button(&control) {
+            if value > 0.5 {
+                self.buttons |= button as u16;
+            } else {
+                self.buttons &= !(button as u16);
+            }
+        }
+    }
+
+    fn merge(&mut self, other: &MacroOutput) {
+        self.buttons |= other.buttons;
+        for (value, other) in self.analog.iter_mut().zip(other.analog) {
+            *value = value.max(other);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RunningMacro {
+    id: Uuid,
+    steps: Vec<MacroStep>,
+    next_step: usize,
+    resume_at: Instant,
+    output: MacroOutput,
+}
+
+impl RunningMacro {
+    fn new(gamepad_macro: &GamepadMacro, now: Instant) -> Self {
+        Self {
+            id: gamepad_macro.id,
+            steps: gamepad_macro.steps.clone(),
+            next_step: 0,
+            resume_at: now,
+            output: MacroOutput::default(),
+        }
+    }
+
+    /// Run every step that is due. Returns false once the macro has finished.
+    fn advance(&mut self, now: Instant) -> bool {
+        while now >= self.resume_at {
+            let Some(step) = self.steps.get(self.next_step) else {
+                return false;
+            };
+            self.next_step += 1;
+
+            match *step {
+                MacroStep::Press { control } => self.output.set(control, 1.0),
+                MacroStep::Release { control } => self.output.set(control, 0.0),
+                MacroStep::Set { control, value } => self.output.set(control, value),
+                MacroStep::Wait { duration_ms } => {
+                    // Scheduled from the previous deadline so frame jitter does not accumulate.
+                    self.resume_at += Duration::from_millis(duration_ms as u64);
+                }
+            }
+        }
+        true
+    }
+}
+
+/// Runs macros of the active sub-profile on the mapping loop clock.
+///
+/// Running macros are cancelled, and their outputs released, whenever the
+/// loop observes a different profile.
+#[derive(Debug, Default)]
+pub struct MacroScheduler {
+    profile: Option<Arc<CompiledProfile>>,
+    running: Vec<RunningMacro>,
+    output: MacroOutput,
+    was_active: bool,
+}
+
+impl MacroScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drop pending start requests (e.g. queued while mapping was stopped).
+    pub fn discard_requests(&mut self) {
+        take_macro_requests();
+    }
+
+    pub fn cancel(&mut self) {
+        self.running.clear();
+        self.output = MacroOutput::default();
+    }
+
+    /// Start requested macros and advance running ones.
+    ///
+    /// Returns whether the outputs must be written this frame: while macros run
+    /// and on the frame after the last one stopped.
+    pub fn update(&mut self, profile: Option<&Arc<CompiledProfile>>, now: Instant) -> bool {
+        let unchanged = match (&self.profile, profile) {
+            (Some(current), Some(profile)) => Arc::ptr_eq(current, profile),
+            (None, None) => true,
+            _ => false,
+        };
+        if !unchanged {
+            self.cancel();
+            self.profile = profile.cloned();
+        }
+
+        let requests = take_macro_requests();
+        if let Some(profile) = profile {
+            for macro_id in requests {
+                let Some(gamepad_macro) = profile.macros.iter().find(|m| m.id == macro_id) else {
+                    continue;
+                };
+                // Retriggering restarts a running macro.
+                self.running.retain(|running| running.id != macro_id);
+                self.running.push(RunningMacro::new(gamepad_macro, now));
+            }
+        }
+
+        let mut output = MacroOutput::default();
+        self.running.retain_mut(|running| {
+            let active = running.advance(now);
+            if active {
+                output.merge(&running.output);
+            }
+            active
+        });
+        self.output = output;
+
+        let active = !self.running.is_empty();
+        let needs_frame = active || self.was_active;
+        self.was_active = active;
+        needs_frame
+    }
+
+    /// Combined outputs of all running macros.
+    pub fn output(&self) -> &MacroOutput {
+        &self.output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile::profiles::HotKey;
+
+    fn tap_a() -> GamepadMacro {
+        GamepadMacro {
+            id: Uuid::new_v4(),
+            name: "Tap A".to_string(),
+            trigger: HotKey {
+                key_name: "F1".to_string(),
+                modifiers: 0,
+            },
+            steps: vec![
+                MacroStep::Press {
+                    control: GamepadControl::ButtonA,
+                },
+                MacroStep::Set {
+                    control: GamepadControl::LeftStickRight,
+                    value: 0.5,
+                },
+                MacroStep::Wait { duration_ms: 50 },
+                MacroStep::Release {
+                    control: GamepadControl::ButtonA,
+                },
+            ],
+            created_at: 0,
+            modified_at: 0,
+        }
+    }
+
+    #[test]
+    fn steps_run_on_schedule() {
+        let gamepad_macro = tap_a();
+        let start = Instant::now();
+        let mut running = RunningMacro::new(&gamepad_macro, start);
+
+        assert!(running.advance(start));
+        assert_eq!(running.output.buttons, 0x1000);
+        assert_eq!(running.output.analog[3], 0.5);
+
+        assert!(running.advance(start + Duration::from_millis(49)));
+        assert_eq!(running.output.buttons, 0x1000);
+
+        assert!(!running.advance(start + Duration::from_millis(50)));
+        assert_eq!(running.output.buttons, 0);
+    }
+}
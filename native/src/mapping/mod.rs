@@ -1,6 +1,26 @@
+pub mod acceleration;
+pub mod button;
+pub mod chord;
+pub mod combine;
 pub mod engine;
+pub mod macros;
+pub mod slew;
+pub mod smoothing;
+pub mod socd;
+pub mod state;
+pub mod steering;
 
+pub use acceleration::*;
+pub use button::*;
+pub use chord::*;
+pub use combine::*;
 pub use engine::*;
+pub use macros::*;
+pub use slew::*;
+pub use smoothing::*;
+pub use socd::*;
+pub use state::*;
+pub use steering::*;
 
 use std::sync::Mutex;
 
@@ -0,0 +1,69 @@
+use crate::profile::profiles::{OutputAxis, OUTPUT_AXIS_COUNT};
+
+/// Limits how fast each output axis may change, using real frame deltas.
+///
+/// Every axis follows its target; axes with a rate move towards it by at most
+/// `rate * delta_secs` per frame.
+#[derive(Debug, Default)]
+pub struct SlewLimiter {
+    outputs: [f32; OUTPUT_AXIS_COUNT], // Indexed by `OutputAxis::index`
+}
+
+impl SlewLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Whether an axis has not returned to rest and must keep being updated.
+    pub fn is_active(&self) -> bool {
+        self.outputs.iter().any(|output| *output != 0.0)
+    }
+
+    /// Move `axis` towards `target` (in [-1.0, 1.0]) and return the limited value.
+    #[inline]
+    pub fn limit(
+        &mut self,
+        axis: OutputAxis,
+        rate: Option<f32>,
+        target: f32,
+        delta_secs: f32,
+    ) -> f32 {
+        let target = target.clamp(-1.0, 1.0);
+        let output = &mut self.outputs[axis.index()];
+        *output = match rate {
+            Some(rate) => {
+                let max_step = rate * delta_secs;
+                *output + (target - *output).clamp(-max_step, max_step)
+            }
+            None => target,
+        };
+        *output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ramps_at_rate() {
+        let mut limiter = SlewLimiter::new();
+        let rate = Some(4.0);
+
+        assert_eq!(limiter.limit(OutputAxis::LeftStickX, rate, 1.0, 0.125), 0.5);
+        assert_eq!(limiter.limit(OutputAxis::LeftStickX, rate, 1.0, 0.125), 1.0);
+        assert_eq!(limiter.limit(OutputAxis::LeftStickX, rate, -1.0, 0.25), 0.0);
+        assert_eq!(
+            limiter.limit(OutputAxis::RightTrigger, None, 0.7, 0.001),
+            0.7
+        );
+        assert!(limiter.is_active());
+
+        limiter.limit(OutputAxis::RightTrigger, None, 0.0, 0.001);
+        assert!(!limiter.is_active());
+    }
+}
@@ -0,0 +1,151 @@
+use crate::profile::profiles::{default_smoothing_beta, default_smoothing_cutoff, SmoothingFilter};
+use std::f32::consts::TAU;
+
+// Cutoff of the One-Euro derivative filter, in Hz.
+const DERIVATIVE_CUTOFF_HZ: f32 = 1.0;
+
+/// Compiled smoothing filter of a mapping's raw analog input.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Smoothing {
+    #[default]
+    None,
+    /// Exponential moving average with a fixed cutoff frequency.
+    Ema { cutoff: f32 },
+    /// One-Euro filter: the cutoff rises with the input speed to limit lag.
+    OneEuro { min_cutoff: f32, beta: f32 },
+}
+
+impl Smoothing {
+    pub fn new(filter: SmoothingFilter, cutoff_hz: f32, beta: f32) -> Self {
+        let cutoff = if cutoff_hz.is_finite() && cutoff_hz > 0.0 {
+            cutoff_hz
+        } else {
+            default_smoothing_cutoff()
+        };
+        let beta = if beta.is_finite() && beta >= 0.0 {
+            beta
+        } else {
+            default_smoothing_beta()
+        };
+
+        match filter {
+            SmoothingFilter::None => Smoothing::None,
+            SmoothingFilter::Ema => Smoothing::Ema { cutoff },
+            SmoothingFilter::OneEuro => Smoothing::OneEuro {
+                min_cutoff: cutoff,
+                beta,
+            },
+        }
+    }
+}
+
+/// Smoothing factor of a first-order low-pass filter for a frame of `delta_secs`.
+#[inline(always)]
+fn low_pass_alpha(cutoff_hz: f32, delta_secs: f32) -> f32 {
+    let tau = 1.0 / (TAU * cutoff_hz);
+    1.0 / (1.0 + tau / delta_secs)
+}
+
+/// Filter state of one key output, carried between frames.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SmoothingState {
+    value: Option<f32>,
+    derivative: f32,
+}
+
+impl SmoothingState {
+    /// Filter a raw reading taken `delta_secs` after the previous one.
+    ///
+    /// A zero reading (key released) passes through and resets the filter: the
+    /// key drops out of the input on the next frame, so the output must settle now.
+    #[inline]
+    pub fn filter(&mut self, smoothing: Smoothing, raw: f32, delta_secs: f32) -> f32 {
+        if raw <= 0.0 {
+            *self = Self::default();
+            return raw;
+        }
+        let Some(previous) = self.value else {
+            // The first reading passes through so a fresh press has no lag.
+            self.value = Some(raw);
+            return raw;
+        };
+        if delta_secs <= 0.0 {
+            return previous;
+        }
+
+        let value = match smoothing {
+            Smoothing::None => raw,
+            Smoothing::Ema { cutoff } => {
+                previous + low_pass_alpha(cutoff, delta_secs) * (raw - previous)
+            }
+            Smoothing::OneEuro { min_cutoff, beta } => {
+                let speed = (raw - previous) / delta_secs;
+                let alpha = low_pass_alpha(DERIVATIVE_CUTOFF_HZ, delta_secs);
+                self.derivative += alpha * (speed - self.derivative);
+
+                let cutoff = min_cutoff + beta * self.derivative.abs();
+                previous + low_pass_alpha(cutoff, delta_secs) * (raw - previous)
+            }
+        };
+
+        self.value = Some(value);
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME: f32 = 1.0 / 120.0;
+
+    #[test]
+    fn ema_damps_jitter() {
+        let smoothing = Smoothing::new(SmoothingFilter::Ema, 5.0, 0.0);
+        let mut state = SmoothingState::default();
+
+        assert_eq!(state.filter(smoothing, 0.5, FRAME), 0.5);
+        let jittered = state.filter(smoothing, 0.52, FRAME);
+        assert!(jittered > 0.5 && jittered < 0.51);
+
+        // Converges on a steady reading.
+        for _ in 0..240 {
+            state.filter(smoothing, 0.8, FRAME);
+        }
+        assert!((state.filter(smoothing, 0.8, FRAME) - 0.8).abs() < 1e-3);
+    }
+
+    #[test]
+    fn one_euro_follows_fast_motion() {
+        let ema = Smoothing::new(SmoothingFilter::Ema, 1.0, 0.0);
+        let one_euro = Smoothing::new(SmoothingFilter::OneEuro, 1.0, 1.0);
+        let (mut ema_state, mut one_euro_state) =
+            (SmoothingState::default(), SmoothingState::default());
+
+        let (mut ema_value, mut one_euro_value) = (0.0, 0.0);
+        for frame in 0..=12 {
+            let raw = frame as f32 / 12.0;
+            ema_value = ema_state.filter(ema, raw, FRAME);
+            one_euro_value = one_euro_state.filter(one_euro, raw, FRAME);
+        }
+        assert!(one_euro_value > ema_value);
+    }
+
+    #[test]
+    fn release_resets_filter() {
+        let smoothing = Smoothing::new(SmoothingFilter::Ema, 5.0, 0.0);
+        let mut state = SmoothingState::default();
+
+        state.filter(smoothing, 1.0, FRAME);
+        assert_eq!(state.filter(smoothing, 0.0, FRAME), 0.0);
+        // The next press starts without lag.
+        assert_eq!(state.filter(smoothing, 0.4, FRAME), 0.4);
+    }
+
+    #[test]
+    fn none_passes_through() {
+        let mut state = SmoothingState::default();
+        state.filter(Smoothing::None, 0.2, FRAME);
+        assert_eq!(state.filter(Smoothing::None, 0.9, FRAME), 0.9);
+    }
+}
@@ -0,0 +1,153 @@
+use crate::profile::profiles::SocdPolicy;
+use serde::{Deserialize, Serialize};
+
+/// Stick axis built from two opposing directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StickAxis {
+    LeftX,
+    LeftY,
+    RightX,
+    RightY,
+}
+
+impl StickAxis {
+    #[inline(always)]
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Resolves simultaneous opposing directions (SOCD) on each stick axis.
+///
+/// Press order is tracked across frames with a monotonic sequence number so
+/// first/last-input-wins policies survive both directions being held.
+#[derive(Debug, Default)]
+pub struct SocdTracker {
+    sequence: u64,
+    // [axis][0 = positive, 1 = negative]; 0 means not pressed.
+    pressed_at: [[u64; 2]; 4],
+}
+
+impl SocdTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Combine the positive and negative direction values of an axis into [-1.0, 1.0].
+    pub fn resolve(
+        &mut self,
+        policy: SocdPolicy,
+        axis: StickAxis,
+        positive: f32,
+        negative: f32,
+    ) -> f32 {
+        let order = &mut self.pressed_at[axis.index()];
+        for (slot, value) in order.iter_mut().zip([positive, negative]) {
+            if value <= 0.0 {
+                *slot = 0;
+            } else if *slot == 0 {
+                self.sequence += 1;
+                *slot = self.sequence;
+            }
+        }
+
+        if positive <= 0.0 || negative <= 0.0 {
+            return (positive - negative).clamp(-1.0, 1.0);
+        }
+
+        let [positive_at, negative_at] = *order;
+        let value = match policy {
+            SocdPolicy::Difference => positive - negative,
+            SocdPolicy::Neutral => 0.0,
+            SocdPolicy::LastInputWins => {
+                if positive_at > negative_at {
+                    positive
+                } else {
+                    -negative
+                }
+            }
+            SocdPolicy::FirstInputWins => {
+                if positive_at < negative_at {
+                    positive
+                } else {
+                    -negative
+                }
+            }
+            SocdPolicy::DeeperPressWins => {
+                if positive > negative {
+                    positive
+                } else if negative > positive {
+                    -negative
+                } else {
+                    0.0
+                }
+            }
+        };
+
+        value.clamp(-1.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_direction_passes_through() {
+        let mut tracker = SocdTracker::new();
+        for policy in [
+            SocdPolicy::Difference,
+            SocdPolicy::LastInputWins,
+            SocdPolicy::FirstInputWins,
+            SocdPolicy::Neutral,
+            SocdPolicy::DeeperPressWins,
+        ] {
+            assert_eq!(tracker.resolve(policy, StickAxis::LeftX, 0.7, 0.0), 0.7);
+            assert_eq!(tracker.resolve(policy, StickAxis::LeftX, 0.0, 0.4), -0.4);
+            assert_eq!(tracker.resolve(policy, StickAxis::LeftX, 0.0, 0.0), 0.0);
+        }
+    }
+
+    #[test]
+    fn press_order_policies() {
+        let mut last = SocdTracker::new();
+        last.resolve(SocdPolicy::LastInputWins, StickAxis::LeftX, 0.0, 1.0);
+        assert_eq!(
+            last.resolve(SocdPolicy::LastInputWins, StickAxis::LeftX, 0.5, 1.0),
+            0.5
+        );
+        // Held across frames, the later press keeps winning.
+        assert_eq!(
+            last.resolve(SocdPolicy::LastInputWins, StickAxis::LeftX, 0.6, 1.0),
+            0.6
+        );
+
+        let mut first = SocdTracker::new();
+        first.resolve(SocdPolicy::FirstInputWins, StickAxis::LeftY, 0.0, 1.0);
+        assert_eq!(
+            first.resolve(SocdPolicy::FirstInputWins, StickAxis::LeftY, 0.5, 1.0),
+            -1.0
+        );
+    }
+
+    #[test]
+    fn value_policies() {
+        let mut tracker = SocdTracker::new();
+        assert_eq!(
+            tracker.resolve(SocdPolicy::Neutral, StickAxis::RightX, 1.0, 1.0),
+            0.0
+        );
+        assert_eq!(
+            tracker.resolve(SocdPolicy::DeeperPressWins, StickAxis::RightX, 0.3, 0.8),
+            -0.8
+        );
+        assert!(
+            (tracker.resolve(SocdPolicy::Difference, StickAxis::RightX, 0.8, 0.3) - 0.5).abs()
+                < 1e-6
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use crate::api::types::AnalogInput;
+use crate::mapping::acceleration::AccelerationState;
+use crate::mapping::button::ButtonModeState;
+use crate::mapping::slew::SlewLimiter;
+use crate::mapping::smoothing::SmoothingState;
+use crate::mapping::socd::SocdTracker;
+use crate::mapping::steering::SteeringTracker;
+use crate::profile::profiles::CompiledProfile;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Runtime state of a single key output, carried between frames of the mapping loop.
+#[derive(Debug, Clone, Default)]
+pub struct KeyState {
+    /// Whether the key is past its engage threshold (dead zone hysteresis).
+    pub engaged: bool,
+    /// Whether an analog-actuated button output is held.
+    pub pressed: bool,
+    /// Rapid-trigger travel extreme (deepest while pressed, shallowest while released).
+    pub travel_extreme: f32,
+    /// Turbo timer and toggle latch of a button output.
+    pub button: ButtonModeState,
+    /// XButtons bit of a button output.
+    pub button_mask: u16,
+    /// Smoothing filter state of the raw reading.
+    pub smoothing: SmoothingState,
+    /// Hold timer of an accelerated output.
+    pub acceleration: AccelerationState,
+}
+
+/// Per-key state owned by the mapping loop.
+///
+/// State is tied to the compiled profile it was built against and is dropped
+/// whenever the loop observes a different profile.
+#[derive(Debug, Default)]
+pub struct MappingState {
+    profile: Option<Arc<CompiledProfile>>,
+    keys: HashMap<(u16, usize), KeyState>, // (key code, output index)
+    /// Press order of opposing stick directions.
+    pub socd: SocdTracker,
+    /// Wheel positions of steering axes.
+    pub steering: SteeringTracker,
+    /// Last value sent on each output axis (slew-rate limiting).
+    pub slew: SlewLimiter,
+    /// Keys whose own mappings are hidden by held chords this frame.
+    pub suppressed_keys: Vec<u16>,
+    analog_buttons: u16, // XButtons held by analog actuation
+    button_resets: u32,  // Gamepad button resets seen so far
+}
+
+impl MappingState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reset all key state if `profile` is not the one the state was built for.
+    ///
+    /// `button_resets` is the gamepad's button reset count: after a reset, toggle
+    /// latches are dropped and held buttons are reported as newly pressed.
+    pub fn sync_profile(&mut self, profile: &Arc<CompiledProfile>, button_resets: u32) {
+        if self.button_resets != button_resets {
+            for state in self.keys.values_mut() {
+                state.button.unlatch();
+            }
+            self.analog_buttons = 0;
+            self.button_resets = button_resets;
+        }
+
+        let unchanged = self
+            .profile
+            .as_ref()
+            .is_some_and(|current| Arc::ptr_eq(current, profile));
+
+        if !unchanged {
+            self.keys.clear();
+            self.socd.reset();
+            self.steering.reset();
+            // Slew-limited outputs carry over so switches ramp like any other change.
+            // Profile switches clear every gamepad button.
+            self.analog_buttons = 0;
+            self.profile = Some(Arc::clone(profile));
+        }
+    }
+
+    /// Whether outputs keep changing with every key released, so frames must run:
+    /// steering and slew-limited axes moving, or inverted outputs resting at full value.
+    pub fn is_settling(&self, profile: Option<&CompiledProfile>) -> bool {
+        self.steering.is_active()
+            || self.slew.is_active()
+            || profile.is_some_and(|profile| !profile.resting_outputs.is_empty())
+    }
+
+    /// Drop the state of keys missing from this frame's input (fully released).
+    /// Latched toggle outputs are kept, released.
+    pub fn release_missing(&mut self, inputs: &[AnalogInput]) {
+        if self.keys.is_empty() {
+            return;
+        }
+
+        self.keys.retain(|(key_code, _), state| {
+            if inputs
+                .iter()
+                .any(|input| input.key_code as u16 == *key_code)
+            {
+                return true;
+            }
+            if !state.button.is_latched() {
+                return false;
+            }
+
+            let (button, button_mask) = (state.button, state.button_mask);
+            *state = KeyState {
+                button,
+                button_mask,
+                ..KeyState::default()
+            };
+            state.button.release();
+            true
+        });
+    }
+
+    /// XButtons latched on by toggle outputs.
+    pub fn latched_buttons(&self) -> u16 {
+        self.keys
+            .values()
+            .filter(|state| state.button.is_latched())
+            .fold(0, |mask, state| mask | state.button_mask)
+    }
+
+    /// Record the analog-actuated buttons held this frame.
+    ///
+    /// Returns the (newly pressed, newly released) button masks relative to the
+    /// previous frame.
+    pub fn update_analog_buttons(&mut self, held: u16) -> (u16, u16) {
+        let previous = std::mem::replace(&mut self.analog_buttons, held);
+        (held & !previous, previous & !held)
+    }
+
+    /// State for output `output` of `key_code`, created on first use.
+    #[inline]
+    pub fn key(&mut self, key_code: u16, output: usize) -> &mut KeyState {
+        self.keys.entry((key_code, output)).or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_profile() -> Arc<CompiledProfile> {
+        Arc::new(CompiledProfile {
+            mappings: HashMap::new(),
+            hotkey: None,
+            stick: Default::default(),
+            socd: Default::default(),
+            combine_modes: Default::default(),
+            stick_modifiers: Vec::new(),
+            steering: [None; 4],
+            slew_rates: [None; 6],
+            layout: Default::default(),
+            resting_outputs: Vec::new(),
+            macros: Vec::new(),
+            chords: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn profile_change_resets_keys() {
+        let mut state = MappingState::new();
+        let first = empty_profile();
+
+        state.sync_profile(&first, 0);
+        state.key(0x57, 0).engaged = true;
+
+        state.sync_profile(&first, 0);
+        assert!(state.key(0x57, 0).engaged);
+
+        state.sync_profile(&empty_profile(), 0);
+        assert!(!state.key(0x57, 0).engaged);
+    }
+
+    #[test]
+    fn missing_keys_are_released() {
+        let mut state = MappingState::new();
+        state.key(0x57, 0).engaged = true;
+        state.key(0x41, 1).engaged = true;
+
+        state.release_missing(&[AnalogInput {
+            key_code: 0x41,
+            analog_value: 0.5,
+        }]);
+
+        assert!(!state.key(0x57, 0).engaged);
+        assert!(state.key(0x41, 1).engaged);
+    }
+
+    #[test]
+    fn analog_button_transitions() {
+        let mut state = MappingState::new();
+        assert_eq!(state.update_analog_buttons(0x1000), (0x1000, 0));
+        assert_eq!(state.update_analog_buttons(0x1000 | 0x2000), (0x2000, 0));
+        assert_eq!(state.update_analog_buttons(0x2000), (0, 0x1000));
+        assert_eq!(state.update_analog_buttons(0), (0, 0x2000));
+    }
+
+    #[test]
+    fn latched_toggles_survive_release() {
+        use crate::mapping::button::ButtonMode;
+
+        let mut state = MappingState::new();
+        let key = state.key(0x57, 0);
+        key.button_mask = 0x1000;
+        key.button
+            .update(ButtonMode::Toggle, true, std::time::Instant::now());
+        state.key(0x41, 0).engaged = true;
+
+        state.release_missing(&[]);
+
+        assert_eq!(state.latched_buttons(), 0x1000);
+        assert!(!state.key(0x41, 0).engaged);
+    }
+
+    #[test]
+    fn button_reset_unlatches_toggles() {
+        use crate::mapping::button::ButtonMode;
+
+        let mut state = MappingState::new();
+        let profile = empty_profile();
+        state.sync_profile(&profile, 0);
+
+        let key = state.key(0x57, 0);
+        key.button_mask = 0x1000;
+        key.button
+            .update(ButtonMode::Toggle, true, std::time::Instant::now());
+        assert_eq!(state.update_analog_buttons(0x1000 | 0x2000), (0x3000, 0));
+
+        // Same profile, but the gamepad buttons were cleared.
+        state.sync_profile(&profile, 1);
+        assert_eq!(state.latched_buttons(), 0);
+        assert_eq!(state.update_analog_buttons(0x2000), (0x2000, 0));
+    }
+}
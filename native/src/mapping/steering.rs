@@ -0,0 +1,99 @@
+use crate::mapping::socd::StickAxis;
+use crate::profile::profiles::{default_steering_return_rate, default_steering_steer_rate};
+
+/// Compiled steering rates of a stick axis, in full locks per second.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Steering {
+    pub steer_rate: f32,  // At full key depth
+    pub return_rate: f32, // Towards centre with no key held
+}
+
+impl Steering {
+    pub fn new(steer_rate: f32, return_rate: f32) -> Self {
+        let steer_rate = if steer_rate.is_finite() && steer_rate > 0.0 {
+            steer_rate
+        } else {
+            default_steering_steer_rate()
+        };
+        let return_rate = if return_rate.is_finite() && return_rate >= 0.0 {
+            return_rate
+        } else {
+            default_steering_return_rate()
+        };
+
+        Self {
+            steer_rate,
+            return_rate,
+        }
+    }
+}
+
+/// Wheel positions of steering axes, carried between frames.
+///
+/// The resolved axis value sets how fast the position moves towards full
+/// lock; with no key held it returns to centre.
+#[derive(Debug, Default)]
+pub struct SteeringTracker {
+    positions: [f32; 4], // Indexed by `StickAxis::index`
+}
+
+impl SteeringTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Whether any axis is off centre and must keep being updated.
+    pub fn is_active(&self) -> bool {
+        self.positions.iter().any(|position| *position != 0.0)
+    }
+
+    /// Advance the position of `axis` by `delta_secs` for an axis value in [-1.0, 1.0].
+    pub fn update(
+        &mut self,
+        steering: Steering,
+        axis: StickAxis,
+        value: f32,
+        delta_secs: f32,
+    ) -> f32 {
+        let position = &mut self.positions[axis.index()];
+        if value != 0.0 {
+            *position += value * steering.steer_rate * delta_secs;
+        } else {
+            let step = steering.return_rate * delta_secs;
+            *position = if position.abs() <= step {
+                0.0
+            } else {
+                *position - step.copysign(*position)
+            };
+        }
+
+        *position = position.clamp(-1.0, 1.0);
+        *position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steers_towards_lock_and_returns() {
+        let steering = Steering::new(2.0, 4.0);
+        let mut tracker = SteeringTracker::new();
+
+        assert_eq!(tracker.update(steering, StickAxis::LeftX, 1.0, 0.25), 0.5);
+        assert_eq!(tracker.update(steering, StickAxis::LeftX, 0.5, 0.25), 0.75);
+        assert_eq!(tracker.update(steering, StickAxis::LeftX, 1.0, 1.0), 1.0);
+        assert!(tracker.is_active());
+
+        assert_eq!(tracker.update(steering, StickAxis::LeftX, 0.0, 0.125), 0.5);
+        assert_eq!(tracker.update(steering, StickAxis::LeftX, 0.0, 0.25), 0.0);
+        assert!(!tracker.is_active());
+
+        assert_eq!(tracker.update(steering, StickAxis::LeftX, -1.0, 0.25), -0.5);
+    }
+}
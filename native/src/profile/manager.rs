@@ -1,5 +1,6 @@
 use crate::conversions::{hotkey_to_metadata_string, metadata_hotkey_to_struct};
 use crate::profile::profiles::*;
+use crate::profile::validation::{validate_mapping, MappingValidation};
 use log::{info, warn};
 use serde_json;
 use std::collections::HashMap;
@@ -28,6 +29,16 @@ pub enum ProfileError {
     ConfigDirError,
     #[error("Profile '{0}' has no sub-profiles")]
     EmptyProfile(String),
+    #[error("Invalid mapping for '{0}': {1}")]
+    InvalidMapping(String, String),
+    #[error("Curve preset not found: {0}")]
+    CurvePresetNotFound(String),
+    #[error("Macro not found: {0}")]
+    MacroNotFound(String),
+    #[error("Chord not found: {0}")]
+    ChordNotFound(String),
+    #[error("A sub-profile supports at most {0} chords")]
+    TooManyChords(usize),
 }
 
 /// Outcome of a sub-profile delete operation.
@@ -61,10 +72,19 @@ pub struct SubProfileMetadata {
     pub name: String,
     pub description: String,
     pub hotkey: Option<String>,
+    pub hotkey_mode: HotkeyMode,
     pub created_at: u64,  // Creation timestamp (Unix)
     pub modified_at: u64, // Modification timestamp (Unix)
 }
 
+/// Sub-profile activated by a held hotkey, restored to `previous_sub_profile_id` on release.
+#[derive(Debug, Clone, Copy)]
+struct MomentaryLayer {
+    profile_id: Uuid,
+    sub_profile_id: Uuid,
+    previous_sub_profile_id: Uuid,
+}
+
 /// Profile manager that keeps one profile loaded in memory at a time.
 pub struct ProfileManager {
     config_dir: PathBuf,
@@ -80,6 +100,12 @@ pub struct ProfileManager {
 
     // Current active sub-profile for the mapping engine.
     current_sub_profile_id: Option<Uuid>,
+
+    // Held sub-profile hotkeys, innermost last.
+    momentary_layers: Vec<MomentaryLayer>,
+
+    // Reject mappings with validation errors instead of storing them.
+    strict_validation: bool,
 }
 
 impl ProfileManager {
@@ -94,6 +120,8 @@ impl ProfileManager {
             current_profile: None,
             compiled_sub_profiles: HashMap::new(),
             current_sub_profile_id: None,
+            momentary_layers: Vec::new(),
+            strict_validation: false,
         };
 
         // Load metadata only for faster startup.
@@ -168,6 +196,7 @@ impl ProfileManager {
                                     .hotkey
                                     .as_ref()
                                     .map(|hk| hotkey_to_metadata_string(hk)),
+                                hotkey_mode: sub_profile.hotkey_mode,
                                 created_at: sub_profile.created_at,
                                 modified_at: sub_profile.modified_at,
                             };
@@ -209,42 +238,30 @@ impl ProfileManager {
             KeyMapping {
                 key_name: "W".to_string(),
                 gamepad_control: GamepadControl::LeftStickUp,
-                response_curve: ResponseCurve::Linear,
-                dead_zone_inner: 0.05,
-                dead_zone_outer: 0.95,
-                curve_params: CurveParams::default(),
                 created_at: base_ts,
                 modified_at: base_ts,
+                ..KeyMapping::default()
             },
             KeyMapping {
                 key_name: "A".to_string(),
                 gamepad_control: GamepadControl::LeftStickLeft,
-                response_curve: ResponseCurve::Linear,
-                dead_zone_inner: 0.05,
-                dead_zone_outer: 0.95,
-                curve_params: CurveParams::default(),
                 created_at: base_ts + 1,
                 modified_at: base_ts + 1,
+                ..KeyMapping::default()
             },
             KeyMapping {
                 key_name: "S".to_string(),
                 gamepad_control: GamepadControl::LeftStickDown,
-                response_curve: ResponseCurve::Linear,
-                dead_zone_inner: 0.05,
-                dead_zone_outer: 0.95,
-                curve_params: CurveParams::default(),
                 created_at: base_ts + 2,
                 modified_at: base_ts + 2,
+                ..KeyMapping::default()
             },
             KeyMapping {
                 key_name: "D".to_string(),
                 gamepad_control: GamepadControl::LeftStickRight,
-                response_curve: ResponseCurve::Linear,
-                dead_zone_inner: 0.05,
-                dead_zone_outer: 0.95,
-                curve_params: CurveParams::default(),
                 created_at: base_ts + 3,
                 modified_at: base_ts + 3,
+                ..KeyMapping::default()
             },
         ];
 
@@ -300,6 +317,7 @@ impl ProfileManager {
                     .hotkey
                     .as_ref()
                     .map(|hk| hotkey_to_metadata_string(hk)),
+                hotkey_mode: sub_profile.hotkey_mode,
                 created_at: sub_profile.created_at,
                 modified_at: sub_profile.modified_at,
             };
@@ -330,6 +348,7 @@ impl ProfileManager {
             self.current_profile = None;
             self.compiled_sub_profiles.clear();
             self.current_sub_profile_id = None;
+            self.momentary_layers.clear();
 
             // Load new profile from disk
             let profile_meta = self
@@ -437,24 +456,93 @@ impl ProfileManager {
         0
     }
 
-    pub fn get_current_mapping(&self, index: usize) -> Option<&KeyMapping> {
+    /// Mapping of the current active sub-profile, with its curve preset's curve resolved.
+    pub fn get_current_mapping(&self, index: usize) -> Option<KeyMapping> {
         if let Some(profile) = &self.current_profile {
             if let Some(sub_id) = self.current_sub_profile_id {
                 if let Some(sub_profile) = profile.sub_profiles.iter().find(|sp| sp.id == sub_id) {
-                    return sub_profile.mappings.get(index);
+                    return sub_profile
+                        .mappings
+                        .get(index)
+                        .map(|mapping| profile.with_resolved_curve(mapping));
                 }
             }
         }
         None
     }
 
+    /// Enable or disable rejection of mappings with validation errors.
+    pub fn set_strict_validation(&mut self, enabled: bool) {
+        self.strict_validation = enabled;
+    }
+
+    pub fn is_strict_validation(&self) -> bool {
+        self.strict_validation
+    }
+
+    /// Validate a mapping with the curve it would use in the current profile
+    /// (its curve preset's, when referenced).
+    pub fn validate_resolved_mapping(&self, mapping: &KeyMapping) -> MappingValidation {
+        match &self.current_profile {
+            Some(profile) => validate_mapping(&profile.with_resolved_curve(mapping)),
+            None => validate_mapping(mapping),
+        }
+    }
+
+    /// Validate every mapping in the current active sub-profile.
+    pub fn validate_current_mappings(&self) -> Vec<MappingValidation> {
+        if let Some(profile) = &self.current_profile {
+            if let Some(sub_id) = self.current_sub_profile_id {
+                if let Some(sub_profile) = profile.sub_profiles.iter().find(|sp| sp.id == sub_id) {
+                    return sub_profile
+                        .mappings
+                        .iter()
+                        .map(|mapping| self.validate_resolved_mapping(mapping))
+                        .collect();
+                }
+            }
+        }
+        Vec::new()
+    }
+
     /// Set/update a mapping in the current active sub-profile.
-    pub fn set_current_mapping(&mut self, mapping: KeyMapping) -> Result<(), ProfileError> {
+    /// A mapping is identified by its key and gamepad control, so one key can drive
+    /// several outputs. `replace_other_outputs` removes the key's mappings to other controls.
+    /// In strict mode, mappings with validation errors are rejected.
+    pub fn set_current_mapping(
+        &mut self,
+        mapping: KeyMapping,
+        replace_other_outputs: bool,
+    ) -> Result<(), ProfileError> {
         let sub_profile_id = self
             .current_sub_profile_id
             .ok_or(ProfileError::NoSubProfileActive)?;
         let sub_profile_name: String;
 
+        if let Some(preset_id) = mapping.curve_preset_id {
+            let profile = self
+                .current_profile
+                .as_ref()
+                .ok_or(ProfileError::NoProfileLoaded)?;
+            if profile.find_curve_preset(&preset_id).is_none() {
+                return Err(ProfileError::CurvePresetNotFound(preset_id.to_string()));
+            }
+        }
+
+        if self.strict_validation {
+            let validation = self.validate_resolved_mapping(&mapping);
+            if validation.has_errors() {
+                let messages: Vec<&str> = validation
+                    .errors()
+                    .map(|issue| issue.message.as_str())
+                    .collect();
+                return Err(ProfileError::InvalidMapping(
+                    mapping.key_name.clone(),
+                    messages.join("; "),
+                ));
+            }
+        }
+
         // Update or add the mapping.
         {
             let profile = self
@@ -477,12 +565,24 @@ impl ProfileManager {
             }
             mapping.modified_at = now;
 
+            if replace_other_outputs {
+                // Keep the creation time of the key's first output when replacing it.
+                if let Some(previous) = sub_profile
+                    .mappings
+                    .iter()
+                    .find(|m| m.key_name == mapping.key_name)
+                {
+                    mapping.created_at = previous.created_at;
+                }
+                sub_profile.mappings.retain(|m| {
+                    m.key_name != mapping.key_name || m.gamepad_control == mapping.gamepad_control
+                });
+            }
+
             // Update or add the mapping.
-            if let Some(existing) = sub_profile
-                .mappings
-                .iter_mut()
-                .find(|m| m.key_name == mapping.key_name)
-            {
+            if let Some(existing) = sub_profile.mappings.iter_mut().find(|m| {
+                m.key_name == mapping.key_name && m.gamepad_control == mapping.gamepad_control
+            }) {
                 mapping.created_at = existing.created_at;
                 *existing = mapping;
             } else {
@@ -514,8 +614,13 @@ impl ProfileManager {
         Ok(())
     }
 
-    /// Remove a mapping from the current active sub-profile by key name.
-    pub fn remove_current_mapping(&mut self, key_name: &str) -> Result<bool, ProfileError> {
+    /// Remove mappings from the current active sub-profile by key name.
+    /// With a gamepad control only that output is removed, otherwise every output of the key.
+    pub fn remove_current_mapping(
+        &mut self,
+        key_name: &str,
+        gamepad_control: Option<GamepadControl>,
+    ) -> Result<bool, ProfileError> {
         let sub_profile_id = self
             .current_sub_profile_id
             .ok_or(ProfileError::NoSubProfileActive)?;
@@ -539,7 +644,10 @@ impl ProfileManager {
 
             // Remove the mapping.
             let initial_len = sub_profile.mappings.len();
-            sub_profile.mappings.retain(|m| m.key_name != key_name);
+            sub_profile.mappings.retain(|m| {
+                m.key_name != key_name
+                    || gamepad_control.is_some_and(|control| m.gamepad_control != control)
+            });
             removed = sub_profile.mappings.len() != initial_len;
 
             if removed {
@@ -568,6 +676,364 @@ impl ProfileManager {
         Ok(removed)
     }
 
+    /// Processing settings of the current active sub-profile.
+    pub fn get_current_sub_profile_settings(&self) -> Option<SubProfileSettings> {
+        let profile = self.current_profile.as_ref()?;
+        let sub_id = self.current_sub_profile_id?;
+        profile
+            .sub_profiles
+            .iter()
+            .find(|sp| sp.id == sub_id)
+            .map(|sp| sp.settings.clone())
+    }
+
+    /// Replace the processing settings of the current active sub-profile.
+    pub fn set_current_sub_profile_settings(
+        &mut self,
+        settings: SubProfileSettings,
+    ) -> Result<(), ProfileError> {
+        let sub_profile_id = self
+            .current_sub_profile_id
+            .ok_or(ProfileError::NoSubProfileActive)?;
+        let sub_profile_name: String;
+
+        {
+            let profile = self
+                .current_profile
+                .as_mut()
+                .ok_or(ProfileError::NoProfileLoaded)?;
+            let sub_profile = profile
+                .sub_profiles
+                .iter_mut()
+                .find(|sp| sp.id == sub_profile_id)
+                .ok_or(ProfileError::SubProfileNotFound(sub_profile_id.to_string()))?;
+
+            sub_profile_name = sub_profile.name.clone();
+            sub_profile.settings = settings;
+
+            let now = crate::profile::profiles::now_timestamp();
+            sub_profile.modified_at = now;
+            profile.modified_at = now;
+        }
+
+        // Recompile and save.
+        {
+            let profile = self
+                .current_profile
+                .as_ref()
+                .ok_or(ProfileError::NoProfileLoaded)?;
+            let compiled = profile
+                .compile_profile(&sub_profile_name)
+                .ok_or_else(|| ProfileError::SubProfileNotFound(sub_profile_name.clone()))?;
+            self.compiled_sub_profiles
+                .insert(sub_profile_id, Arc::new(compiled));
+
+            let profile_clone = profile.clone();
+            self.save_profile(&profile_clone)?;
+        }
+
+        Ok(())
+    }
+
+    /// Macros of the current active sub-profile.
+    pub fn get_current_macros(&self) -> Vec<GamepadMacro> {
+        let Some(profile) = self.current_profile.as_ref() else {
+            return Vec::new();
+        };
+        let Some(sub_id) = self.current_sub_profile_id else {
+            return Vec::new();
+        };
+        profile
+            .sub_profiles
+            .iter()
+            .find(|sp| sp.id == sub_id)
+            .map(|sp| sp.macros.clone())
+            .unwrap_or_default()
+    }
+
+    /// Create or update a macro in the current active sub-profile.
+    pub fn set_current_macro(&mut self, gamepad_macro: GamepadMacro) -> Result<Uuid, ProfileError> {
+        let mut gamepad_macro = gamepad_macro;
+        if gamepad_macro.id.is_nil() {
+            gamepad_macro.id = Uuid::new_v4();
+        }
+        let macro_id = gamepad_macro.id;
+
+        self.modify_current_sub_profile(|sub_profile, now| {
+            gamepad_macro.modified_at = now;
+            if let Some(existing) = sub_profile.macros.iter_mut().find(|m| m.id == macro_id) {
+                gamepad_macro.created_at = existing.created_at;
+                *existing = gamepad_macro;
+            } else {
+                gamepad_macro.created_at = now;
+                sub_profile.macros.push(gamepad_macro);
+            }
+            Ok(())
+        })?;
+
+        Ok(macro_id)
+    }
+
+    /// Delete a macro from the current active sub-profile.
+    pub fn delete_current_macro(&mut self, macro_id: &Uuid) -> Result<(), ProfileError> {
+        self.modify_current_sub_profile(|sub_profile, _now| {
+            let index = sub_profile
+                .macros
+                .iter()
+                .position(|m| m.id == *macro_id)
+                .ok_or_else(|| ProfileError::MacroNotFound(macro_id.to_string()))?;
+            sub_profile.macros.remove(index);
+            Ok(())
+        })
+    }
+
+    /// Chords of the current active sub-profile.
+    pub fn get_current_chords(&self) -> Vec<ChordMapping> {
+        let Some(profile) = self.current_profile.as_ref() else {
+            return Vec::new();
+        };
+        let Some(sub_id) = self.current_sub_profile_id else {
+            return Vec::new();
+        };
+        profile
+            .sub_profiles
+            .iter()
+            .find(|sp| sp.id == sub_id)
+            .map(|sp| sp.chords.clone())
+            .unwrap_or_default()
+    }
+
+    /// Create or update a chord in the current active sub-profile.
+    pub fn set_current_chord(&mut self, chord: ChordMapping) -> Result<Uuid, ProfileError> {
+        let mut chord = chord;
+        if chord.id.is_nil() {
+            chord.id = Uuid::new_v4();
+        }
+        let chord_id = chord.id;
+
+        self.modify_current_sub_profile(|sub_profile, now| {
+            chord.modified_at = now;
+            if let Some(existing) = sub_profile.chords.iter_mut().find(|c| c.id == chord_id) {
+                chord.created_at = existing.created_at;
+                *existing = chord;
+            } else if sub_profile.chords.len() >= crate::mapping::MAX_CHORDS {
+                return Err(ProfileError::TooManyChords(crate::mapping::MAX_CHORDS));
+            } else {
+                chord.created_at = now;
+                sub_profile.chords.push(chord);
+            }
+            Ok(())
+        })?;
+
+        Ok(chord_id)
+    }
+
+    /// Delete a chord from the current active sub-profile.
+    pub fn delete_current_chord(&mut self, chord_id: &Uuid) -> Result<(), ProfileError> {
+        self.modify_current_sub_profile(|sub_profile, _now| {
+            let index = sub_profile
+                .chords
+                .iter()
+                .position(|c| c.id == *chord_id)
+                .ok_or_else(|| ProfileError::ChordNotFound(chord_id.to_string()))?;
+            sub_profile.chords.remove(index);
+            Ok(())
+        })
+    }
+
+    /// Apply `modify` to the current active sub-profile, then recompile and save.
+    fn modify_current_sub_profile<F>(&mut self, modify: F) -> Result<(), ProfileError>
+    where
+        F: FnOnce(&mut SubProfile, u64) -> Result<(), ProfileError>,
+    {
+        let sub_profile_id = self
+            .current_sub_profile_id
+            .ok_or(ProfileError::NoSubProfileActive)?;
+        let sub_profile_name: String;
+
+        {
+            let profile = self
+                .current_profile
+                .as_mut()
+                .ok_or(ProfileError::NoProfileLoaded)?;
+            let sub_profile = profile
+                .sub_profiles
+                .iter_mut()
+                .find(|sp| sp.id == sub_profile_id)
+                .ok_or(ProfileError::SubProfileNotFound(sub_profile_id.to_string()))?;
+
+            let now = crate::profile::profiles::now_timestamp();
+            modify(sub_profile, now)?;
+            sub_profile_name = sub_profile.name.clone();
+            sub_profile.modified_at = now;
+            profile.modified_at = now;
+        }
+
+        let profile = self
+            .current_profile
+            .as_ref()
+            .ok_or(ProfileError::NoProfileLoaded)?;
+        let compiled = profile
+            .compile_profile(&sub_profile_name)
+            .ok_or_else(|| ProfileError::SubProfileNotFound(sub_profile_name.clone()))?;
+        self.compiled_sub_profiles
+            .insert(sub_profile_id, Arc::new(compiled));
+
+        let profile_clone = profile.clone();
+        self.save_profile(&profile_clone)
+    }
+
+    /// Curve presets defined by the current profile.
+    pub fn get_curve_presets(&self) -> Vec<CurvePreset> {
+        self.current_profile
+            .as_ref()
+            .map(|profile| profile.curve_presets.clone())
+            .unwrap_or_default()
+    }
+
+    /// Create or update a curve preset in the current profile.
+    /// Every sub-profile referencing the preset is recompiled.
+    pub fn set_curve_preset(&mut self, preset: CurvePreset) -> Result<Uuid, ProfileError> {
+        if self.strict_validation {
+            self.validate_preset_mappings(&preset)?;
+        }
+
+        let preset_id;
+
+        {
+            let profile = self
+                .current_profile
+                .as_mut()
+                .ok_or(ProfileError::NoProfileLoaded)?;
+
+            let now = crate::profile::profiles::now_timestamp();
+            let mut preset = preset;
+            if preset.id.is_nil() {
+                preset.id = Uuid::new_v4();
+            }
+            preset.modified_at = now;
+            preset_id = preset.id;
+
+            if let Some(existing) = profile.curve_presets.iter_mut().find(|p| p.id == preset.id) {
+                preset.created_at = existing.created_at;
+                *existing = preset;
+            } else {
+                preset.created_at = now;
+                profile.curve_presets.push(preset);
+            }
+
+            profile.modified_at = now;
+        }
+
+        self.recompile_sub_profiles_using_preset(&preset_id)?;
+        Ok(preset_id)
+    }
+
+    /// Reject a preset update that gives a mapping using it validation errors.
+    fn validate_preset_mappings(&self, preset: &CurvePreset) -> Result<(), ProfileError> {
+        let Some(profile) = &self.current_profile else {
+            return Ok(());
+        };
+
+        let mappings = profile
+            .sub_profiles
+            .iter()
+            .flat_map(|sub_profile| &sub_profile.mappings)
+            .filter(|mapping| mapping.curve_preset_id == Some(preset.id));
+        for mapping in mappings {
+            let validation = validate_mapping(&KeyMapping {
+                response_curve: preset.response_curve,
+                curve_params: preset.curve_params.clone(),
+                ..mapping.clone()
+            });
+            if validation.has_errors() {
+                let messages: Vec<&str> = validation
+                    .errors()
+                    .map(|issue| issue.message.as_str())
+                    .collect();
+                return Err(ProfileError::InvalidMapping(
+                    mapping.key_name.clone(),
+                    messages.join("; "),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete a curve preset from the current profile.
+    /// Mappings that referenced it keep the preset curve as their own.
+    pub fn delete_curve_preset(&mut self, preset_id: &Uuid) -> Result<(), ProfileError> {
+        {
+            let profile = self
+                .current_profile
+                .as_mut()
+                .ok_or(ProfileError::NoProfileLoaded)?;
+            let index = profile
+                .curve_presets
+                .iter()
+                .position(|p| p.id == *preset_id)
+                .ok_or_else(|| ProfileError::CurvePresetNotFound(preset_id.to_string()))?;
+            let preset = profile.curve_presets.remove(index);
+
+            let now = crate::profile::profiles::now_timestamp();
+            for sub_profile in &mut profile.sub_profiles {
+                for mapping in &mut sub_profile.mappings {
+                    if mapping.curve_preset_id == Some(*preset_id) {
+                        mapping.curve_preset_id = None;
+                        mapping.response_curve = preset.response_curve;
+                        mapping.curve_params = preset.curve_params.clone();
+                        mapping.modified_at = now;
+                    }
+                }
+            }
+
+            profile.modified_at = now;
+        }
+
+        // Curves are unchanged, so compiled sub-profiles stay valid.
+        if let Some(profile) = &self.current_profile {
+            let profile_clone = profile.clone();
+            self.save_profile(&profile_clone)?;
+        }
+
+        Ok(())
+    }
+
+    /// Recompile every sub-profile of the current profile that references a preset, then save.
+    fn recompile_sub_profiles_using_preset(
+        &mut self,
+        preset_id: &Uuid,
+    ) -> Result<usize, ProfileError> {
+        let profile = self
+            .current_profile
+            .as_ref()
+            .ok_or(ProfileError::NoProfileLoaded)?;
+
+        let mut recompiled = 0;
+        for sub_profile in &profile.sub_profiles {
+            if !sub_profile.uses_curve_preset(preset_id) {
+                continue;
+            }
+
+            let compiled = profile
+                .compile_profile(&sub_profile.name)
+                .ok_or_else(|| ProfileError::SubProfileNotFound(sub_profile.id.to_string()))?;
+            self.compiled_sub_profiles
+                .insert(sub_profile.id, Arc::new(compiled));
+            recompiled += 1;
+        }
+
+        info!(
+            "[PROFILE] Curve preset {} updated, recompiled {} sub-profiles",
+            preset_id, recompiled
+        );
+
+        let profile_clone = profile.clone();
+        self.save_profile(&profile_clone)?;
+
+        Ok(recompiled)
+    }
+
     /// Delete a profile by UUID (removes from disk and metadata).
     pub fn delete_profile(&mut self, profile_id: &Uuid) -> Result<(), ProfileError> {
         // Get profile metadata to find file path.
@@ -583,6 +1049,7 @@ impl ProfileManager {
                 self.current_profile = None;
                 self.current_sub_profile_id = None;
                 self.compiled_sub_profiles.clear();
+                self.momentary_layers.clear();
             }
         }
 
@@ -711,6 +1178,63 @@ impl ProfileManager {
         Ok(())
     }
 
+    /// Activate a sub-profile while its hold hotkey is down.
+    /// Returns false if the profile is not loaded or the sub-profile is already active.
+    pub fn activate_momentary_sub_profile(
+        &mut self,
+        profile_id: &Uuid,
+        sub_profile_id: &Uuid,
+    ) -> Result<bool, ProfileError> {
+        if self.get_current_profile_id() != Some(*profile_id) {
+            return Ok(false);
+        }
+        let Some(previous_sub_profile_id) = self.current_sub_profile_id else {
+            return Ok(false);
+        };
+        if previous_sub_profile_id == *sub_profile_id {
+            return Ok(false);
+        }
+
+        self.switch_profile(profile_id, sub_profile_id)?;
+        self.momentary_layers.push(MomentaryLayer {
+            profile_id: *profile_id,
+            sub_profile_id: *sub_profile_id,
+            previous_sub_profile_id,
+        });
+        Ok(true)
+    }
+
+    /// Release a held sub-profile hotkey.
+    /// Returns the restored sub-profile, or `None` if nothing had to be switched back
+    /// (another layer is still held on top, or the sub-profile was switched away from).
+    pub fn release_momentary_sub_profile(
+        &mut self,
+        profile_id: &Uuid,
+        sub_profile_id: &Uuid,
+    ) -> Result<Option<Uuid>, ProfileError> {
+        let Some(position) = self.momentary_layers.iter().rposition(|layer| {
+            layer.profile_id == *profile_id && layer.sub_profile_id == *sub_profile_id
+        }) else {
+            return Ok(None);
+        };
+        let layer = self.momentary_layers.remove(position);
+
+        // Released out of order: the layer above now restores to what this one replaced.
+        if let Some(above) = self.momentary_layers.get_mut(position) {
+            above.previous_sub_profile_id = layer.previous_sub_profile_id;
+            return Ok(None);
+        }
+
+        if self.get_current_profile_id() != Some(*profile_id)
+            || self.current_sub_profile_id != Some(*sub_profile_id)
+        {
+            return Ok(None);
+        }
+
+        self.switch_profile(profile_id, &layer.previous_sub_profile_id)?;
+        Ok(Some(layer.previous_sub_profile_id))
+    }
+
     /// Cycle through sub-profiles of a profile based on creation timestamp order.
     pub fn cycle_sub_profile(&mut self, profile_id: &Uuid) -> Result<(Uuid, String), ProfileError> {
         let mut sub_metas: Vec<SubProfileMetadata> = self
@@ -810,6 +1334,7 @@ impl ProfileManager {
                     name: sub_profile.name.clone(),
                     description: sub_profile.description.clone(),
                     hotkey: hotkey_string,
+                    hotkey_mode: sub_profile.hotkey_mode,
                     created_at: sub_profile.created_at,
                     modified_at: sub_profile.modified_at,
                 };
@@ -872,6 +1397,7 @@ impl ProfileManager {
             name: sub_profile.name.clone(),
             description: sub_profile.description.clone(),
             hotkey: hotkey_string,
+            hotkey_mode: sub_profile.hotkey_mode,
             created_at: sub_profile.created_at,
             modified_at: sub_profile.modified_at,
         };
@@ -898,6 +1424,8 @@ impl ProfileManager {
                     self.current_sub_profile_id = None;
                     self.compiled_sub_profiles.clear();
                 }
+                // Held layers may restore to the removed sub-profile.
+                self.momentary_layers.clear();
 
                 // Remove from profile and update timestamps.
                 current.sub_profiles.retain(|sp| sp.id != *sub_profile_id);
@@ -1199,6 +1727,7 @@ impl ProfileManager {
         profile_id: &Uuid,
         sub_profile_id: &Uuid,
         hotkey: Option<&str>,
+        hotkey_mode: Option<HotkeyMode>,
     ) -> Result<(), ProfileError> {
         let parsed_hotkey = hotkey.and_then(metadata_hotkey_to_struct);
         let now_timestamp = crate::profile::profiles::now_timestamp();
@@ -1213,6 +1742,9 @@ impl ProfileManager {
             sub_meta.hotkey = parsed_hotkey
                 .as_ref()
                 .map(|hk| hotkey_to_metadata_string(hk));
+            if let Some(mode) = hotkey_mode {
+                sub_meta.hotkey_mode = mode;
+            }
             sub_meta.modified_at = now_timestamp;
         } else {
             return Err(ProfileError::SubProfileNotFound(sub_profile_id.to_string()));
@@ -1229,6 +1761,9 @@ impl ProfileManager {
                     .find(|sp| sp.id == *sub_profile_id)
                 {
                     sub_profile.hotkey = parsed_hotkey.clone();
+                    if let Some(mode) = hotkey_mode {
+                        sub_profile.hotkey_mode = mode;
+                    }
                     sub_profile.modified_at = now_timestamp;
                     current.modified_at = now_timestamp;
 
@@ -1251,6 +1786,9 @@ impl ProfileManager {
             .find(|sp| sp.id == *sub_profile_id)
         {
             sub_profile.hotkey = parsed_hotkey.clone();
+            if let Some(mode) = hotkey_mode {
+                sub_profile.hotkey_mode = mode;
+            }
             sub_profile.modified_at = now_timestamp;
             profile.modified_at = now_timestamp;
             self.save_profile(&profile)?;
@@ -1396,5 +1934,73 @@ fn ensure_profile_ids(profile: &mut GameProfile) {
         if sub.id.is_nil() {
             sub.id = Uuid::new_v4();
         }
+        for gamepad_macro in &mut sub.macros {
+            if gamepad_macro.id.is_nil() {
+                gamepad_macro.id = Uuid::new_v4();
+            }
+        }
+        for chord in &mut sub.chords {
+            if chord.id.is_nil() {
+                chord.id = Uuid::new_v4();
+            }
+        }
+    }
+    for preset in &mut profile.curve_presets {
+        if preset.id.is_nil() {
+            preset.id = Uuid::new_v4();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strict_manager(profile: GameProfile) -> ProfileManager {
+        ProfileManager {
+            config_dir: PathBuf::new(),
+            profile_metadata: HashMap::new(),
+            sub_profile_metadata: HashMap::new(),
+            current_profile: Some(profile),
+            compiled_sub_profiles: HashMap::new(),
+            current_sub_profile_id: None,
+            momentary_layers: Vec::new(),
+            strict_validation: true,
+        }
+    }
+
+    #[test]
+    fn strict_manager_rejects_invalid_preset_update() {
+        let preset = CurvePreset {
+            id: Uuid::new_v4(),
+            name: "Gentle".to_string(),
+            response_curve: ResponseCurve::Power,
+            curve_params: CurveParams::default(),
+            created_at: 0,
+            modified_at: 0,
+        };
+        let mut profile = GameProfile::new("Test".to_string());
+        profile.curve_presets.push(preset.clone());
+        profile.sub_profiles[0].mappings.push(KeyMapping {
+            key_name: "W".to_string(),
+            curve_preset_id: Some(preset.id),
+            ..KeyMapping::default()
+        });
+        let mut manager = strict_manager(profile);
+
+        let invalid = CurvePreset {
+            curve_params: CurveParams {
+                strength: -1.0,
+                ..CurveParams::default()
+            },
+            ..preset.clone()
+        };
+        assert!(matches!(
+            manager.set_curve_preset(invalid),
+            Err(ProfileError::InvalidMapping(key_name, _)) if key_name == "W"
+        ));
+
+        let stored = &manager.current_profile.as_ref().unwrap().curve_presets[0];
+        assert_eq!(stored.curve_params.strength, preset.curve_params.strength);
     }
 }
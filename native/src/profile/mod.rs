@@ -1,7 +1,11 @@
 pub mod manager;
 pub mod profiles;
 pub mod sync;
+pub mod validation;
 
 pub use manager::*;
 pub use profiles::*;
 pub use sync::update_systems_after_profile_switch;
+pub use validation::{
+    validate_mapping, MappingValidation, ValidationCode, ValidationIssue, ValidationSeverity,
+};
@@ -1,5 +1,14 @@
-use crate::curves::{CurveProcessor, UnifiedCurve};
-use log::debug;
+use crate::curves::{
+    ButtonActuation, CurveProcessor, DeadZoneHysteresis, OutputRange, StickDirection, StickLayout,
+    StickMagnitudeModifier, StickShaper, UnifiedCurve,
+};
+use crate::mapping::acceleration::{Acceleration, AccelerationState};
+use crate::mapping::button::ButtonMode;
+use crate::mapping::chord::MAX_CHORDS;
+use crate::mapping::smoothing::Smoothing;
+use crate::mapping::socd::StickAxis;
+use crate::mapping::steering::Steering;
+use log::{debug, warn};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -31,6 +40,77 @@ pub struct GameProfile {
     pub modified_at: u64,
     #[serde(default)]
     pub hotkey: Option<HotKey>,
+    #[serde(default)]
+    pub curve_presets: Vec<CurvePreset>,
+}
+
+/// Named curve shared by mappings of a profile through `KeyMapping::curve_preset_id`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurvePreset {
+    #[serde(default = "generate_uuid")]
+    pub id: Uuid,
+    pub name: String,
+    pub response_curve: ResponseCurve,
+    pub curve_params: CurveParams,
+    #[serde(default = "now_timestamp")]
+    pub created_at: u64,
+    #[serde(default = "now_timestamp")]
+    pub modified_at: u64,
+}
+
+/// Timed sequence of gamepad actions started by a key or hotkey.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GamepadMacro {
+    #[serde(default = "generate_uuid")]
+    pub id: Uuid,
+    pub name: String,
+    pub trigger: HotKey, // Key with optional modifiers
+    pub steps: Vec<MacroStep>,
+    #[serde(default = "now_timestamp")]
+    pub created_at: u64,
+    #[serde(default = "now_timestamp")]
+    pub modified_at: u64,
+}
+
+/// Single macro action. Steps run back to back until a `Wait`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "action", rename_all = "camelCase")]
+pub enum MacroStep {
+    /// Press a button, or drive an analog control to full value.
+    Press {
+        control: GamepadControl,
+    },
+    Release {
+        control: GamepadControl,
+    },
+    /// Drive an analog control to `value` (0.0 - 1.0); buttons press above 0.5.
+    Set {
+        control: GamepadControl,
+        value: f32,
+    },
+    Wait {
+        #[serde(rename = "durationMs")]
+        duration_ms: u32,
+    },
+}
+
+/// Gamepad outputs driven while a set of keys is held together.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChordMapping {
+    #[serde(default = "generate_uuid")]
+    pub id: Uuid,
+    pub name: String,
+    pub keys: Vec<String>, // Key names, at least two
+    pub outputs: Vec<GamepadControl>,
+    #[serde(default)]
+    pub suppress_keys: bool, // Hide the keys' own mappings while the chord is held
+    #[serde(default = "now_timestamp")]
+    pub created_at: u64,
+    #[serde(default = "now_timestamp")]
+    pub modified_at: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,13 +121,139 @@ pub struct SubProfile {
     pub name: String,
     pub description: String,
     pub hotkey: Option<HotKey>,
+    #[serde(default)]
+    pub hotkey_mode: HotkeyMode,
     pub mappings: Vec<KeyMapping>,
+    #[serde(default)]
+    pub settings: SubProfileSettings,
+    #[serde(default)]
+    pub macros: Vec<GamepadMacro>,
+    #[serde(default)]
+    pub chords: Vec<ChordMapping>,
     #[serde(default = "now_timestamp")]
     pub created_at: u64,
     #[serde(default = "now_timestamp")]
     pub modified_at: u64,
 }
 
+/// Processing options shared by all mappings of a sub-profile.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubProfileSettings {
+    #[serde(default)]
+    pub stick_shape: StickShape,
+    #[serde(default)]
+    pub radial_dead_zone: f32, // Dead zone on stick magnitude (0.0 - 1.0)
+    #[serde(default)]
+    pub socd_policy: SocdPolicy,
+    #[serde(default)]
+    pub combine_modes: HashMap<GamepadControl, CombineMode>, // Controls not listed use Max
+    #[serde(default)]
+    pub stick_modifiers: Vec<StickModifier>, // Keys scaling stick magnitude while held
+    #[serde(default)]
+    pub steering: HashMap<StickAxis, SteeringSettings>, // Axes using steering accumulation
+    #[serde(default)]
+    pub slew_rates: HashMap<OutputAxis, f32>, // Maximum change per second, axes not listed are unlimited
+    #[serde(default)]
+    pub swap_sticks: bool, // Left stick output drives the right stick and vice versa
+    #[serde(default)]
+    pub invert_left_y: bool, // Applied to the output stick, after swapping
+    #[serde(default)]
+    pub invert_right_y: bool,
+}
+
+/// Gamepad output axis, after directions are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OutputAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+/// Number of gamepad output axes.
+pub const OUTPUT_AXIS_COUNT: usize = 6;
+
+impl OutputAxis {
+    #[inline(always)]
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Steering-wheel accumulation of a stick axis: key depth sets the turn rate.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SteeringSettings {
+    #[serde(default = "default_steering_steer_rate")]
+    pub steer_rate: f32, // Full locks per second at full key depth
+    #[serde(default = "default_steering_return_rate")]
+    pub return_rate: f32, // Full locks per second back to centre when released
+}
+
+impl Default for SteeringSettings {
+    fn default() -> Self {
+        Self {
+            steer_rate: default_steering_steer_rate(),
+            return_rate: default_steering_return_rate(),
+        }
+    }
+}
+
+pub fn default_steering_steer_rate() -> f32 {
+    2.0
+}
+
+pub fn default_steering_return_rate() -> f32 {
+    4.0
+}
+
+/// Key that scales stick output magnitude while held (e.g. walk/sprint).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StickModifier {
+    pub key_name: String,
+    #[serde(default)]
+    pub stick: StickSide,
+    #[serde(default = "default_stick_modifier_scale")]
+    pub scale: f32, // Magnitude multiplier (0.0 - 2.0)
+}
+
+/// Sticks affected by a stick modifier.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum StickSide {
+    #[default]
+    Left,
+    Right,
+    Both,
+}
+
+pub fn default_stick_modifier_scale() -> f32 {
+    0.4
+}
+
+/// Resolution of opposing directions on the same stick axis held together.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SocdPolicy {
+    #[default]
+    Difference, // positive - negative (analog values cancel out)
+    LastInputWins,
+    FirstInputWins,
+    Neutral,
+    DeeperPressWins,
+}
+
+/// How combined stick axes are shaped before being sent to the gamepad.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum StickShape {
+    #[default]
+    Square, // Independent axes, diagonals reach (1, 1)
+    CircularClamp,  // Magnitude clamped to 1.0
+    SquareToCircle, // Elliptical grid mapping of the unit square onto the unit circle
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct KeyMapping {
@@ -56,21 +262,131 @@ pub struct KeyMapping {
     pub response_curve: ResponseCurve,
     pub dead_zone_inner: f32, // Inner dead zone (0.0 - 1.0)
     pub dead_zone_outer: f32, // Outer dead zone (0.0 - 1.0)
+    #[serde(default)]
+    pub dead_zone_hysteresis: f32, // Release band below the inner dead zone (0.0 = disabled)
     pub curve_params: CurveParams,
+    #[serde(default)]
+    pub curve_preset_id: Option<Uuid>, // Shared preset overriding response_curve/curve_params
+    #[serde(default)]
+    pub output_min: f32, // Anti-deadzone: minimum output once active (0.0 - 1.0)
+    #[serde(default = "default_output_max")]
+    pub output_max: f32, // Maximum output (0.0 - 1.0)
+    #[serde(default = "default_output_gain")]
+    pub output_gain: f32, // Multiplier applied to the curve output before range mapping
+    #[serde(default)]
+    pub analog_actuation: bool, // Buttons: fire from the analog depth instead of OS key events
+    #[serde(default = "default_actuation_depth")]
+    pub actuation_depth: f32, // Buttons: depth that presses the button (0.0 - 1.0)
+    #[serde(default = "default_release_depth")]
+    pub release_depth: f32, // Buttons: depth below which the button releases (0.0 - 1.0)
+    #[serde(default)]
+    pub rapid_trigger: bool, // Buttons: release/re-press on travel direction changes
+    #[serde(default = "default_rapid_trigger_sensitivity")]
+    pub rapid_trigger_sensitivity: f32, // Buttons: travel that flips the rapid-trigger state
+    #[serde(default)]
+    pub button_behavior: ButtonBehavior, // Buttons: normal, turbo or toggle
+    #[serde(default = "default_turbo_rate")]
+    pub turbo_rate: f32, // Buttons: turbo presses per second
+    #[serde(default)]
+    pub stick_angle: f32, // Stick angle controls: degrees counter-clockwise from right (90 = up)
+    #[serde(default)]
+    pub smoothing: SmoothingFilter, // Filter applied to the raw analog reading
+    #[serde(default = "default_smoothing_cutoff")]
+    pub smoothing_cutoff: f32, // EMA cutoff / One-Euro minimum cutoff, in Hz
+    #[serde(default = "default_smoothing_beta")]
+    pub smoothing_beta: f32, // One-Euro cutoff increase per unit of input speed
+    #[serde(default)]
+    pub invert_output: bool, // Analog controls: output 1 - value (released key = full output)
+    #[serde(default)]
+    pub invert_direction: bool, // Stick controls: push the opposite direction
+    #[serde(default)]
+    pub acceleration: bool, // Stick controls: output gain ramps up while the key is held
+    #[serde(default = "default_acceleration_onset_ms")]
+    pub acceleration_onset_ms: u32, // Hold time before the gain starts to rise
+    #[serde(default = "default_acceleration_ramp_ms")]
+    pub acceleration_ramp_ms: u32, // Time from onset to full boost
+    #[serde(default = "default_acceleration_max_boost")]
+    pub acceleration_max_boost: f32, // Extra gain at full boost (1.0 doubles the output)
     #[serde(default = "now_timestamp")]
     pub created_at: u64,
     #[serde(default = "now_timestamp")]
     pub modified_at: u64,
 }
 
+pub fn default_output_max() -> f32 {
+    1.0
+}
+
+pub fn default_output_gain() -> f32 {
+    1.0
+}
+
+pub fn default_actuation_depth() -> f32 {
+    0.5
+}
+
+pub fn default_release_depth() -> f32 {
+    0.4
+}
+
+pub fn default_rapid_trigger_sensitivity() -> f32 {
+    0.1
+}
+
+pub fn default_turbo_rate() -> f32 {
+    10.0
+}
+
+pub fn default_smoothing_cutoff() -> f32 {
+    5.0
+}
+
+pub fn default_smoothing_beta() -> f32 {
+    0.5
+}
+
+pub fn default_acceleration_onset_ms() -> u32 {
+    150
+}
+
+pub fn default_acceleration_ramp_ms() -> u32 {
+    400
+}
+
+pub fn default_acceleration_max_boost() -> f32 {
+    1.0
+}
+
 impl KeyMapping {
     /// Get VK code for internal use (EventInputManager, WootingSDK)
     pub fn get_vk_code(&self) -> u16 {
         crate::conversions::key_name_to_vk(&self.key_name)
     }
+
+    /// Whether a button mapping is driven by the mapping loop from the analog depth
+    /// instead of OS key events (analog actuation, rapid trigger, turbo and toggle).
+    pub fn uses_analog_actuation(&self) -> bool {
+        (self.analog_actuation
+            || self.rapid_trigger
+            || self.button_behavior != ButtonBehavior::Normal)
+            && self.gamepad_control.is_button()
+    }
+
+    fn compile_actuation(&self) -> Option<ButtonActuation> {
+        if !self.uses_analog_actuation() {
+            return None;
+        }
+
+        let actuation = ButtonActuation::new(self.actuation_depth, self.release_depth);
+        Some(if self.rapid_trigger {
+            actuation.with_rapid_trigger(self.rapid_trigger_sensitivity)
+        } else {
+            actuation
+        })
+    }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum GamepadControl {
     LeftStickUp,
     LeftStickDown,
@@ -92,12 +408,145 @@ pub enum GamepadControl {
     DPadDown,
     DPadLeft,
     DPadRight,
+    Start,
+    Back,
+    Guide,
+    LeftThumb,       // Left stick click
+    RightThumb,      // Right stick click
+    LeftStickAngle,  // Left stick along the mapping's `stick_angle`
+    RightStickAngle, // Right stick along the mapping's `stick_angle`
+}
+
+/// Number of analog controls (stick directions and triggers) driven by the mapping loop.
+pub const ANALOG_CONTROL_COUNT: usize = 10;
+
+impl GamepadControl {
+    /// Dense index of an analog control, `None` for digital buttons.
+    #[inline(always)]
+    pub fn analog_index(&self) -> Option<usize> {
+        match self {
+            GamepadControl::LeftStickUp => Some(0),
+            GamepadControl::LeftStickDown => Some(1),
+            GamepadControl::LeftStickLeft => Some(2),
+            GamepadControl::LeftStickRight => Some(3),
+            GamepadControl::RightStickUp => Some(4),
+            GamepadControl::RightStickDown => Some(5),
+            GamepadControl::RightStickLeft => Some(6),
+            GamepadControl::RightStickRight => Some(7),
+            GamepadControl::LeftTrigger => Some(8),
+            GamepadControl::RightTrigger => Some(9),
+            _ => None,
+        }
+    }
+
+    /// Whether the control pushes a stick along an arbitrary angle.
+    #[inline(always)]
+    pub fn is_stick_angle(&self) -> bool {
+        matches!(
+            self,
+            GamepadControl::LeftStickAngle | GamepadControl::RightStickAngle
+        )
+    }
+
+    /// The stick direction pointing the other way; other controls are unchanged.
+    pub fn opposite(&self) -> GamepadControl {
+        match self {
+            GamepadControl::LeftStickUp => GamepadControl::LeftStickDown,
+            GamepadControl::LeftStickDown => GamepadControl::LeftStickUp,
+            GamepadControl::LeftStickLeft => GamepadControl::LeftStickRight,
+            GamepadControl::LeftStickRight => GamepadControl::LeftStickLeft,
+            GamepadControl::RightStickUp => GamepadControl::RightStickDown,
+            GamepadControl::RightStickDown => GamepadControl::RightStickUp,
+            GamepadControl::RightStickLeft => GamepadControl::RightStickRight,
+            GamepadControl::RightStickRight => GamepadControl::RightStickLeft,
+            other => *other,
+        }
+    }
+
+    /// Whether the control moves a stick (a direction or an angle).
+    #[inline(always)]
+    pub fn is_stick(&self) -> bool {
+        matches!(
+            self,
+            GamepadControl::LeftStickUp
+                | GamepadControl::LeftStickDown
+                | GamepadControl::LeftStickLeft
+                | GamepadControl::LeftStickRight
+                | GamepadControl::RightStickUp
+                | GamepadControl::RightStickDown
+                | GamepadControl::RightStickLeft
+                | GamepadControl::RightStickRight
+                | GamepadControl::LeftStickAngle
+                | GamepadControl::RightStickAngle
+        )
+    }
+
+    /// Whether the control is a digital button.
+    #[inline(always)]
+    pub fn is_button(&self) -> bool {
+        self.analog_index().is_none() && !self.is_stick_angle()
+    }
+}
+
+/// Smoothing filter applied to a mapping's raw analog reading.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SmoothingFilter {
+    #[default]
+    None,
+    Ema,     // Exponential moving average
+    OneEuro, // Speed-adaptive low-pass (less lag on fast motion)
+}
+
+/// Behaviour of a button mapping while its key is held.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ButtonBehavior {
+    #[default]
+    Normal,
+    Turbo,  // Auto-repeat at `turbo_rate` while held
+    Toggle, // Press to latch, press again to release
+}
+
+/// How the values of several keys mapped to the same analog control are combined.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CombineMode {
+    #[default]
+    Max,
+    SumClamped, // Sum of active keys, clamped to 1.0
+    Average,    // Mean of active keys
+    Priority,   // First active key in sub-profile mapping order
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum ResponseCurve {
     Linear,
     Custom,
+    Power,       // x^strength
+    Exponential, // (e^(strength*x) - 1) / (e^strength - 1)
+    SCurve,      // x^strength / (x^strength + (1-x)^strength)
+    Logarithmic, // ln(1 + strength*x) / ln(1 + strength)
+    Bezier,      // Cubic Bezier from (0,0) to (1,1) shaped by two control handles
+}
+
+impl ResponseCurve {
+    /// Whether the curve is generated from `CurveParams::strength` instead of custom points.
+    pub fn is_parametric(&self) -> bool {
+        matches!(
+            self,
+            ResponseCurve::Power
+                | ResponseCurve::Exponential
+                | ResponseCurve::SCurve
+                | ResponseCurve::Logarithmic
+        )
+    }
+}
+
+pub fn default_curve_strength() -> f32 {
+    2.0
+}
+
+/// Handles at 1/3 and 2/3 of the diagonal produce a straight line.
+pub fn default_bezier_handles() -> [(f32, f32); 2] {
+    [(1.0 / 3.0, 1.0 / 3.0), (2.0 / 3.0, 2.0 / 3.0)]
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -105,6 +554,10 @@ pub enum ResponseCurve {
 pub struct CurveParams {
     pub use_smooth_interpolation: bool, // For custom curves: true=smooth, false=linear
     pub custom_points: Vec<(f32, f32)>, // Custom curve points
+    #[serde(default = "default_curve_strength")]
+    pub strength: f32, // Exponent/strength for parametric curves
+    #[serde(default = "default_bezier_handles")]
+    pub bezier_handles: [(f32, f32); 2], // Bezier control handles (P1, P2)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -121,16 +574,55 @@ impl HotKey {
     }
 }
 
+/// How a sub-profile hotkey activates its sub-profile.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum HotkeyMode {
+    #[default]
+    Switch, // Switch on press and stay
+    Hold, // Active while held, previous sub-profile restored on release
+}
+
 #[derive(Debug, Clone)]
 pub struct CompiledProfile {
-    pub mappings: HashMap<u16, CompiledMapping>,
+    pub mappings: HashMap<u16, Vec<CompiledMapping>>, // One entry per output driven by the key
     pub hotkey: Option<HotKey>,
+    pub stick: StickShaper,
+    pub socd: SocdPolicy,
+    pub combine_modes: [CombineMode; ANALOG_CONTROL_COUNT], // Indexed by `analog_index`
+    pub stick_modifiers: Vec<StickMagnitudeModifier>,
+    pub steering: [Option<Steering>; 4], // Indexed by `StickAxis::index`
+    pub slew_rates: [Option<f32>; OUTPUT_AXIS_COUNT], // Indexed by `OutputAxis::index`
+    pub layout: StickLayout,
+    pub resting_outputs: Vec<(u16, usize)>, // (key code, output index) of inverted analog outputs
+    pub macros: Vec<GamepadMacro>,
+    pub chords: Vec<CompiledChord>,
+}
+
+/// Chord of the compiled profile.
+///
+/// The event manager holds a chord once the OS reports all its keys down and
+/// drives its buttons; the mapping loop drives its analog outputs while it is
+/// held, at the depth of the shallowest chord key.
+#[derive(Debug, Clone)]
+pub struct CompiledChord {
+    pub key_codes: Vec<[u16; 2]>, // Left and right variants of each key; distinct, at least two
+    pub outputs: Vec<GamepadControl>,
+    pub suppress_keys: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct CompiledMapping {
     pub gamepad_control: GamepadControl,
     pub curve: UnifiedCurve,
+    pub output: OutputRange,
+    pub hysteresis: Option<DeadZoneHysteresis>,
+    pub priority: u16, // Position in the sub-profile, lower wins in `CombineMode::Priority`
+    pub actuation: Option<ButtonActuation>, // Set for buttons driven by the analog depth
+    pub button_mode: ButtonMode,
+    pub direction: Option<StickDirection>, // Set for stick angle controls
+    pub smoothing: Smoothing,
+    pub invert_output: bool,
+    pub acceleration: Option<Acceleration>, // Stick controls only
 }
 pub type CurveFunction = fn(f32) -> f32;
 
@@ -139,6 +631,8 @@ impl Default for CurveParams {
         Self {
             use_smooth_interpolation: false,
             custom_points: Vec::new(),
+            strength: default_curve_strength(),
+            bezier_handles: default_bezier_handles(),
         }
     }
 }
@@ -152,7 +646,29 @@ impl Default for KeyMapping {
             response_curve: ResponseCurve::Linear,
             dead_zone_inner: 0.05,
             dead_zone_outer: 0.95,
+            dead_zone_hysteresis: 0.0,
             curve_params: CurveParams::default(),
+            curve_preset_id: None,
+            output_min: 0.0,
+            output_max: default_output_max(),
+            output_gain: default_output_gain(),
+            analog_actuation: false,
+            actuation_depth: default_actuation_depth(),
+            release_depth: default_release_depth(),
+            rapid_trigger: false,
+            rapid_trigger_sensitivity: default_rapid_trigger_sensitivity(),
+            button_behavior: ButtonBehavior::default(),
+            turbo_rate: default_turbo_rate(),
+            stick_angle: 0.0,
+            smoothing: SmoothingFilter::default(),
+            smoothing_cutoff: default_smoothing_cutoff(),
+            smoothing_beta: default_smoothing_beta(),
+            invert_output: false,
+            invert_direction: false,
+            acceleration: false,
+            acceleration_onset_ms: default_acceleration_onset_ms(),
+            acceleration_ramp_ms: default_acceleration_ramp_ms(),
+            acceleration_max_boost: default_acceleration_max_boost(),
             created_at: now,
             modified_at: now,
         }
@@ -171,9 +687,36 @@ impl GameProfile {
             created_at: now,
             modified_at: now,
             hotkey: None,
+            curve_presets: Vec::new(),
+        }
+    }
+
+    pub fn find_curve_preset(&self, preset_id: &Uuid) -> Option<&CurvePreset> {
+        self.curve_presets.iter().find(|p| p.id == *preset_id)
+    }
+
+    /// Copy of a mapping with the curve it is compiled with (see `resolve_curve`).
+    pub fn with_resolved_curve(&self, mapping: &KeyMapping) -> KeyMapping {
+        let (response_curve, curve_params) = self.resolve_curve(mapping);
+        KeyMapping {
+            response_curve,
+            curve_params: curve_params.clone(),
+            ..mapping.clone()
         }
     }
 
+    /// Curve used by a mapping: its preset when referenced and present, otherwise its own.
+    pub fn resolve_curve<'a>(
+        &'a self,
+        mapping: &'a KeyMapping,
+    ) -> (ResponseCurve, &'a CurveParams) {
+        mapping
+            .curve_preset_id
+            .and_then(|id| self.find_curve_preset(&id))
+            .map(|preset| (preset.response_curve, &preset.curve_params))
+            .unwrap_or((mapping.response_curve, &mapping.curve_params))
+    }
+
     pub fn compile_profile(&self, sub_profile_name: &str) -> Option<CompiledProfile> {
         let sub_profile = self
             .sub_profiles
@@ -181,31 +724,191 @@ impl GameProfile {
             .find(|sp| sp.name == sub_profile_name)?;
 
         let mut mappings = HashMap::new();
+        let mut resting_outputs = Vec::new();
+
+        for (position, mapping) in sub_profile.mappings.iter().enumerate() {
+            if let Some(preset_id) = mapping.curve_preset_id {
+                if self.find_curve_preset(&preset_id).is_none() {
+                    warn!(
+                        "[PROFILE] Mapping '{}' references missing curve preset {}, using its own curve",
+                        mapping.key_name, preset_id
+                    );
+                }
+            }
+
+            let (response_curve, curve_params) = self.resolve_curve(mapping);
 
-        for mapping in &sub_profile.mappings {
             debug!(
-                "[PROFILE] Compiling mapping '{}': curve={:?}, {} custom points, smooth={}",
+                "[PROFILE] Compiling mapping '{}': curve={:?}, {} custom points, smooth={}, strength={}",
                 mapping.key_name,
-                mapping.response_curve,
-                mapping.curve_params.custom_points.len(),
-                mapping.curve_params.use_smooth_interpolation
+                response_curve,
+                curve_params.custom_points.len(),
+                curve_params.use_smooth_interpolation,
+                curve_params.strength
             );
 
+            let validation =
+                crate::profile::validation::validate_mapping(&self.with_resolved_curve(mapping));
+            for issue in validation.errors() {
+                warn!(
+                    "[PROFILE] Mapping '{}' in '{}': {}",
+                    mapping.key_name, sub_profile.name, issue.message
+                );
+            }
+
+            // Direction inversion resolves to the opposite control (or angle).
+            let (gamepad_control, stick_angle) = if mapping.invert_direction {
+                (
+                    mapping.gamepad_control.opposite(),
+                    mapping.stick_angle + 180.0,
+                )
+            } else {
+                (mapping.gamepad_control, mapping.stick_angle)
+            };
+            let invert_output = mapping.invert_output && !gamepad_control.is_button();
+
             let compiled = CompiledMapping {
-                gamepad_control: mapping.gamepad_control,
+                gamepad_control,
                 curve: UnifiedCurve::new(
-                    mapping.response_curve,
-                    mapping.curve_params.clone(),
+                    response_curve,
+                    curve_params.clone(),
                     mapping.dead_zone_inner,
                     mapping.dead_zone_outer,
                 ),
+                output: OutputRange::new(
+                    mapping.output_min,
+                    mapping.output_max,
+                    mapping.output_gain,
+                ),
+                hysteresis: DeadZoneHysteresis::new(
+                    mapping.dead_zone_inner,
+                    mapping.dead_zone_hysteresis,
+                ),
+                priority: position.min(u16::MAX as usize) as u16,
+                actuation: mapping.compile_actuation(),
+                button_mode: ButtonMode::new(mapping.button_behavior, mapping.turbo_rate),
+                smoothing: Smoothing::new(
+                    mapping.smoothing,
+                    mapping.smoothing_cutoff,
+                    mapping.smoothing_beta,
+                ),
+                direction: gamepad_control.is_stick_angle().then(|| {
+                    StickDirection::new(
+                        gamepad_control == GamepadControl::RightStickAngle,
+                        stick_angle,
+                    )
+                }),
+                invert_output,
+                acceleration: (mapping.acceleration && gamepad_control.is_stick()).then(|| {
+                    Acceleration::new(
+                        mapping.acceleration_onset_ms,
+                        mapping.acceleration_ramp_ms,
+                        mapping.acceleration_max_boost,
+                    )
+                }),
             };
-            mappings.insert(mapping.get_vk_code(), compiled);
+            let vk_code = mapping.get_vk_code();
+            let outputs = mappings.entry(vk_code).or_insert_with(Vec::new);
+            if invert_output {
+                resting_outputs.push((vk_code, outputs.len()));
+            }
+            outputs.push(compiled);
+        }
+
+        let mut combine_modes = [CombineMode::Max; ANALOG_CONTROL_COUNT];
+        for (control, mode) in &sub_profile.settings.combine_modes {
+            if let Some(index) = control.analog_index() {
+                combine_modes[index] = *mode;
+            }
+        }
+
+        let mut stick_modifiers = Vec::new();
+        for modifier in &sub_profile.settings.stick_modifiers {
+            let vk_code = crate::conversions::key_name_to_vk(&modifier.key_name);
+            if vk_code == 0 {
+                warn!(
+                    "[PROFILE] Stick modifier key '{}' in '{}' is unknown, skipping",
+                    modifier.key_name, sub_profile.name
+                );
+                continue;
+            }
+            stick_modifiers.push(StickMagnitudeModifier::new(
+                crate::conversions::vk_side_variants(vk_code),
+                modifier.stick,
+                modifier.scale,
+            ));
+        }
+
+        let mut chords = Vec::new();
+        for chord in &sub_profile.chords {
+            if chords.len() == MAX_CHORDS {
+                warn!(
+                    "[PROFILE] '{}' has more than {} chords, skipping the rest",
+                    sub_profile.name, MAX_CHORDS
+                );
+                break;
+            }
+            let mut key_codes: Vec<u16> = chord
+                .keys
+                .iter()
+                .map(|key_name| crate::conversions::key_name_to_vk(key_name))
+                .collect();
+            key_codes.sort_unstable();
+            key_codes.dedup();
+            if key_codes.len() < 2 || key_codes.contains(&0) {
+                warn!(
+                    "[PROFILE] Chord '{}' in '{}' needs at least two known keys, skipping",
+                    chord.name, sub_profile.name
+                );
+                continue;
+            }
+            chords.push(CompiledChord {
+                key_codes: key_codes
+                    .into_iter()
+                    .map(crate::conversions::vk_side_variants)
+                    .collect(),
+                outputs: chord.outputs.clone(),
+                suppress_keys: chord.suppress_keys,
+            });
+        }
+
+        let mut steering = [None; 4];
+        for (axis, settings) in &sub_profile.settings.steering {
+            steering[axis.index()] = Some(Steering::new(settings.steer_rate, settings.return_rate));
+        }
+
+        let mut slew_rates = [None; OUTPUT_AXIS_COUNT];
+        for (axis, rate) in &sub_profile.settings.slew_rates {
+            if rate.is_finite() && *rate > 0.0 {
+                slew_rates[axis.index()] = Some(*rate);
+            } else {
+                warn!(
+                    "[PROFILE] Slew rate {} for {:?} in '{}' is not positive, ignoring",
+                    rate, axis, sub_profile.name
+                );
+            }
         }
 
         Some(CompiledProfile {
             mappings,
             hotkey: sub_profile.hotkey.clone(),
+            stick: StickShaper::new(
+                sub_profile.settings.stick_shape,
+                sub_profile.settings.radial_dead_zone,
+            ),
+            socd: sub_profile.settings.socd_policy,
+            combine_modes,
+            stick_modifiers,
+            steering,
+            slew_rates,
+            layout: StickLayout {
+                swap_sticks: sub_profile.settings.swap_sticks,
+                invert_left_y: sub_profile.settings.invert_left_y,
+                invert_right_y: sub_profile.settings.invert_right_y,
+            },
+            resting_outputs,
+            macros: sub_profile.macros.clone(),
+            chords,
         })
     }
 }
@@ -223,11 +926,21 @@ impl SubProfile {
             name,
             description,
             hotkey,
+            hotkey_mode: HotkeyMode::default(),
             mappings,
+            settings: SubProfileSettings::default(),
+            macros: Vec::new(),
+            chords: Vec::new(),
             created_at: now,
             modified_at: now,
         }
     }
+
+    pub fn uses_curve_preset(&self, preset_id: &Uuid) -> bool {
+        self.mappings
+            .iter()
+            .any(|m| m.curve_preset_id == Some(*preset_id))
+    }
 }
 
 impl Default for SubProfile {
@@ -245,9 +958,53 @@ impl Default for SubProfile {
 }
 
 impl CompiledMapping {
-    /// Apply dead zones and curve transformation to input value.
+    /// Apply dead zones, curve transformation and output range to input value.
     #[inline(always)]
     pub fn process_input(&self, raw_value: f32) -> f32 {
-        self.curve.process_input(raw_value)
+        self.output.apply(self.curve.process_input(raw_value))
+    }
+
+    /// Value of an analog output for this frame's reading, `delta_secs` after the
+    /// previous one. Accelerated while held and inverted when configured.
+    /// `engaged` and `acceleration` are the per-key states owned by the caller.
+    #[inline(always)]
+    pub fn analog_output(
+        &self,
+        raw_value: f32,
+        engaged: &mut bool,
+        acceleration: &mut AccelerationState,
+        delta_secs: f32,
+    ) -> f32 {
+        let mut value = self.process_input_with_state(raw_value, engaged);
+        if let Some(compiled) = self.acceleration {
+            value = acceleration.apply(compiled, value, delta_secs);
+        }
+        if self.invert_output {
+            1.0 - value
+        } else {
+            value
+        }
+    }
+
+    /// Like `process_input`, but keeps the key engaged until it falls below the
+    /// hysteresis release threshold. The curve still starts at the inner dead zone;
+    /// an engaged key never outputs less than the output minimum, and never 0, so
+    /// it holds a steady value inside the release band.
+    /// `engaged` is the per-key state owned by the caller.
+    #[inline(always)]
+    pub fn process_input_with_state(&self, raw_value: f32, engaged: &mut bool) -> f32 {
+        let Some(hysteresis) = self.hysteresis else {
+            return self.process_input(raw_value);
+        };
+
+        *engaged = hysteresis.update(raw_value, *engaged);
+        if !*engaged {
+            return 0.0;
+        }
+
+        let floor = DeadZoneHysteresis::MIN_ENGAGED_OUTPUT
+            .max(self.output.min)
+            .min(self.output.max);
+        self.process_input(raw_value).max(floor)
     }
 }
@@ -3,7 +3,7 @@ use crate::{mapping::MAPPING_ENGINE, ATOMIC_GAMEPAD_STATE, EVENT_INPUT_MANAGER,
 /// Single source of truth for refreshing systems after a profile switch.
 /// ArcSwap ensures thread-safe updates without pausing the mapping loop.
 pub fn update_systems_after_profile_switch() {
-    ATOMIC_GAMEPAD_STATE.set_buttons(0);
+    ATOMIC_GAMEPAD_STATE.reset_buttons();
 
     let manager_guard = PROFILE_MANAGER.lock().unwrap();
     if let Some(ref manager) = *manager_guard {
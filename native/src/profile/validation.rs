@@ -0,0 +1,540 @@
+//! Structured validation of key mappings (dead zones, curve points, curve parameters).
+
+use crate::curves::MAX_CURVE_STRENGTH;
+use crate::mapping::button::MAX_TURBO_RATE_HZ;
+use crate::profile::profiles::{ButtonBehavior, KeyMapping, ResponseCurve, SmoothingFilter};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationSeverity {
+    /// The mapping works, but the stored value is adjusted or likely unintended.
+    Warning,
+    /// The mapping cannot behave as configured.
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationCode {
+    NonFiniteValue,
+    DeadZoneOutOfRange,
+    InvertedDeadZones,
+    InvalidHysteresis,
+    MissingCustomPoints,
+    PointOutOfRange,
+    DuplicatePointX,
+    UnsortedPoints,
+    NonMonotonicPoints,
+    InvalidStrength,
+    HandleOutOfRange,
+    OutputOutOfRange,
+    InvalidActuation,
+    InvalidButtonBehavior,
+    InvalidSmoothing,
+    InvalidInversion,
+    InvalidAcceleration,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    pub severity: ValidationSeverity,
+    pub code: ValidationCode,
+    pub message: String,
+}
+
+/// Validation result for a single mapping.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MappingValidation {
+    pub key_name: String,
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl MappingValidation {
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.severity == ValidationSeverity::Error)
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == ValidationSeverity::Error)
+    }
+
+    fn push(&mut self, severity: ValidationSeverity, code: ValidationCode, message: String) {
+        self.issues.push(ValidationIssue {
+            severity,
+            code,
+            message,
+        });
+    }
+}
+
+fn in_unit_range(value: f32) -> bool {
+    (0.0..=1.0).contains(&value)
+}
+
+/// Validate dead zones, curve definition and output range of a mapping.
+pub fn validate_mapping(mapping: &KeyMapping) -> MappingValidation {
+    use ValidationCode::*;
+    use ValidationSeverity::{Error, Warning};
+
+    let mut result = MappingValidation {
+        key_name: mapping.key_name.clone(),
+        issues: Vec::new(),
+    };
+
+    // Dead zones.
+    let inner = mapping.dead_zone_inner;
+    let outer = mapping.dead_zone_outer;
+    if !inner.is_finite() || !outer.is_finite() {
+        result.push(
+            Error,
+            NonFiniteValue,
+            "Dead zones must be finite numbers".to_string(),
+        );
+    } else {
+        if !in_unit_range(inner) || !in_unit_range(outer) {
+            result.push(
+                Error,
+                DeadZoneOutOfRange,
+                format!(
+                    "Dead zones must be within [0, 1] (inner={}, outer={})",
+                    inner, outer
+                ),
+            );
+        }
+        if inner >= outer {
+            result.push(
+                Error,
+                InvertedDeadZones,
+                format!(
+                    "Inner dead zone ({}) must be lower than outer dead zone ({})",
+                    inner, outer
+                ),
+            );
+        }
+    }
+
+    let hysteresis = mapping.dead_zone_hysteresis;
+    if !hysteresis.is_finite() {
+        result.push(
+            Error,
+            NonFiniteValue,
+            "Dead zone hysteresis must be a finite number".to_string(),
+        );
+    } else if hysteresis < 0.0 {
+        result.push(
+            Warning,
+            InvalidHysteresis,
+            format!(
+                "Negative dead zone hysteresis ({}) disables the band",
+                hysteresis
+            ),
+        );
+    } else if inner.is_finite() && hysteresis > inner {
+        result.push(
+            Warning,
+            InvalidHysteresis,
+            format!(
+                "Dead zone hysteresis ({}) exceeds the inner dead zone ({}) and releases at 0",
+                hysteresis, inner
+            ),
+        );
+    }
+
+    // Curve definition.
+    let params = &mapping.curve_params;
+    match mapping.response_curve {
+        ResponseCurve::Custom => {
+            let points = &params.custom_points;
+            if points.is_empty() {
+                result.push(
+                    Warning,
+                    MissingCustomPoints,
+                    "Custom curve has no points and behaves as linear".to_string(),
+                );
+            }
+
+            if points.iter().any(|(x, y)| !x.is_finite() || !y.is_finite()) {
+                result.push(
+                    Error,
+                    NonFiniteValue,
+                    "Custom curve points must be finite numbers".to_string(),
+                );
+            } else {
+                if let Some((x, y)) = points
+                    .iter()
+                    .find(|(x, y)| !in_unit_range(*x) || !in_unit_range(*y))
+                {
+                    result.push(
+                        Error,
+                        PointOutOfRange,
+                        format!("Curve point ({}, {}) is outside [0, 1]", x, y),
+                    );
+                }
+
+                if points.windows(2).any(|pair| pair[1].0 < pair[0].0) {
+                    result.push(
+                        Warning,
+                        UnsortedPoints,
+                        "Curve points are not sorted by input and will be reordered".to_string(),
+                    );
+                }
+
+                let mut sorted = points.clone();
+                sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+                if let Some(pair) = sorted
+                    .windows(2)
+                    .find(|pair| (pair[1].0 - pair[0].0).abs() < 1e-6)
+                {
+                    result.push(
+                        Error,
+                        DuplicatePointX,
+                        format!("Multiple curve points share input {}", pair[0].0),
+                    );
+                }
+
+                if sorted.windows(2).any(|pair| pair[1].1 < pair[0].1) {
+                    result.push(
+                        Warning,
+                        NonMonotonicPoints,
+                        "Curve output decreases as input increases".to_string(),
+                    );
+                }
+            }
+        }
+        ResponseCurve::Bezier => {
+            let handles = &params.bezier_handles;
+            if handles
+                .iter()
+                .any(|(x, y)| !x.is_finite() || !y.is_finite())
+            {
+                result.push(
+                    Error,
+                    NonFiniteValue,
+                    "Bezier handles must be finite numbers".to_string(),
+                );
+            } else if handles
+                .iter()
+                .any(|(x, y)| !in_unit_range(*x) || !in_unit_range(*y))
+            {
+                result.push(
+                    Warning,
+                    HandleOutOfRange,
+                    "Bezier handles outside [0, 1] are clamped to keep the curve monotonic"
+                        .to_string(),
+                );
+            }
+        }
+        curve if curve.is_parametric() => {
+            let strength = params.strength;
+            if !strength.is_finite() {
+                result.push(
+                    Error,
+                    NonFiniteValue,
+                    "Curve strength must be a finite number".to_string(),
+                );
+            } else if strength <= 0.0
+                && matches!(curve, ResponseCurve::Power | ResponseCurve::SCurve)
+            {
+                result.push(
+                    Error,
+                    InvalidStrength,
+                    format!(
+                        "{:?} curve requires a positive strength ({})",
+                        curve, strength
+                    ),
+                );
+            } else if strength < 0.0 && curve == ResponseCurve::Logarithmic {
+                result.push(
+                    Warning,
+                    InvalidStrength,
+                    format!(
+                        "Logarithmic curve ignores negative strength ({}) and behaves as linear",
+                        strength
+                    ),
+                );
+            } else if strength.abs() > MAX_CURVE_STRENGTH {
+                result.push(
+                    Warning,
+                    InvalidStrength,
+                    format!(
+                        "Curve strength ({}) is limited to ±{}",
+                        strength, MAX_CURVE_STRENGTH
+                    ),
+                );
+            }
+        }
+        _ => {}
+    }
+
+    // Output range.
+    let (output_min, output_max, gain) =
+        (mapping.output_min, mapping.output_max, mapping.output_gain);
+    if !output_min.is_finite() || !output_max.is_finite() || !gain.is_finite() {
+        result.push(
+            Error,
+            NonFiniteValue,
+            "Output range and gain must be finite numbers".to_string(),
+        );
+    } else if !in_unit_range(output_min)
+        || !in_unit_range(output_max)
+        || output_min > output_max
+        || gain < 0.0
+    {
+        result.push(
+            Warning,
+            OutputOutOfRange,
+            format!(
+                "Output range is adjusted to fit [0, 1] (min={}, max={}, gain={})",
+                output_min, output_max, gain
+            ),
+        );
+    }
+
+    // Analog button actuation (also used by turbo and toggle).
+    let has_button_behavior = mapping.button_behavior != ButtonBehavior::Normal;
+    if mapping.analog_actuation || mapping.rapid_trigger || has_button_behavior {
+        let (press, release) = (mapping.actuation_depth, mapping.release_depth);
+        let sensitivity = mapping.rapid_trigger_sensitivity;
+        if !mapping.gamepad_control.is_button() {
+            result.push(
+                Warning,
+                InvalidActuation,
+                format!(
+                    "Button actuation and behaviours only apply to buttons and are ignored for {:?}",
+                    mapping.gamepad_control
+                ),
+            );
+        } else if !press.is_finite() || !release.is_finite() || !sensitivity.is_finite() {
+            result.push(
+                Error,
+                NonFiniteValue,
+                "Actuation depths and rapid-trigger sensitivity must be finite numbers".to_string(),
+            );
+        } else if mapping.rapid_trigger && (sensitivity <= 0.0 || sensitivity > 1.0) {
+            result.push(
+                Error,
+                InvalidActuation,
+                format!(
+                    "Rapid-trigger sensitivity must be within (0, 1] ({})",
+                    sensitivity
+                ),
+            );
+        } else if press <= 0.0 || !in_unit_range(press) || !in_unit_range(release) {
+            result.push(
+                Error,
+                InvalidActuation,
+                format!(
+                    "Actuation depth must be within (0, 1] and release depth within [0, 1] (actuation={}, release={})",
+                    press, release
+                ),
+            );
+        } else if release > press {
+            result.push(
+                Warning,
+                InvalidActuation,
+                format!(
+                    "Release depth ({}) exceeds the actuation depth ({}) and is lowered to match",
+                    release, press
+                ),
+            );
+        }
+    }
+
+    // Turbo rate.
+    if mapping.button_behavior == ButtonBehavior::Turbo && mapping.gamepad_control.is_button() {
+        let rate = mapping.turbo_rate;
+        if !rate.is_finite() {
+            result.push(
+                Error,
+                NonFiniteValue,
+                "Turbo rate must be a finite number".to_string(),
+            );
+        } else if rate <= 0.0 {
+            result.push(
+                Error,
+                InvalidButtonBehavior,
+                format!("Turbo rate must be positive ({})", rate),
+            );
+        } else if rate > MAX_TURBO_RATE_HZ {
+            result.push(
+                Warning,
+                InvalidButtonBehavior,
+                format!(
+                    "Turbo rate ({}) is limited to {} presses per second",
+                    rate, MAX_TURBO_RATE_HZ
+                ),
+            );
+        }
+    }
+
+    // Smoothing filter.
+    if mapping.smoothing != SmoothingFilter::None {
+        let (cutoff, beta) = (mapping.smoothing_cutoff, mapping.smoothing_beta);
+        if !cutoff.is_finite() || !beta.is_finite() {
+            result.push(
+                Error,
+                NonFiniteValue,
+                "Smoothing cutoff and beta must be finite numbers".to_string(),
+            );
+        } else if cutoff <= 0.0 || beta < 0.0 {
+            result.push(
+                Error,
+                InvalidSmoothing,
+                format!(
+                    "Smoothing cutoff must be positive and beta not negative (cutoff={}, beta={})",
+                    cutoff, beta
+                ),
+            );
+        }
+    }
+
+    // Inversion.
+    let control = mapping.gamepad_control;
+    if mapping.invert_output && control.is_button() {
+        result.push(
+            Warning,
+            InvalidInversion,
+            format!(
+                "Output inversion only applies to analog controls and is ignored for {:?}",
+                control
+            ),
+        );
+    }
+    if mapping.invert_direction && control.opposite() == control && !control.is_stick_angle() {
+        result.push(
+            Warning,
+            InvalidInversion,
+            format!(
+                "Direction inversion only applies to stick controls and is ignored for {:?}",
+                control
+            ),
+        );
+    }
+
+    // Acceleration.
+    if mapping.acceleration {
+        let max_boost = mapping.acceleration_max_boost;
+        if !max_boost.is_finite() {
+            result.push(
+                Error,
+                NonFiniteValue,
+                "Acceleration boost must be a finite number".to_string(),
+            );
+        } else if max_boost < 0.0 {
+            result.push(
+                Error,
+                InvalidAcceleration,
+                format!("Acceleration boost must not be negative ({})", max_boost),
+            );
+        }
+        if !control.is_stick() {
+            result.push(
+                Warning,
+                InvalidAcceleration,
+                format!(
+                    "Acceleration only applies to stick controls and is ignored for {:?}",
+                    control
+                ),
+            );
+        }
+    }
+
+    // Stick angle.
+    if mapping.gamepad_control.is_stick_angle() && !mapping.stick_angle.is_finite() {
+        result.push(
+            Error,
+            NonFiniteValue,
+            "Stick angle must be a finite number".to_string(),
+        );
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile::profiles::CurveParams;
+
+    fn custom_mapping(points: Vec<(f32, f32)>) -> KeyMapping {
+        KeyMapping {
+            key_name: "W".to_string(),
+            response_curve: ResponseCurve::Custom,
+            curve_params: CurveParams {
+                custom_points: points,
+                ..CurveParams::default()
+            },
+            ..KeyMapping::default()
+        }
+    }
+
+    fn codes(validation: &MappingValidation) -> Vec<ValidationCode> {
+        validation.issues.iter().map(|issue| issue.code).collect()
+    }
+
+    #[test]
+    fn default_mapping_is_clean() {
+        let validation = validate_mapping(&KeyMapping::default());
+        assert!(validation.issues.is_empty());
+    }
+
+    #[test]
+    fn inverted_dead_zones_are_errors() {
+        let mapping = KeyMapping {
+            dead_zone_inner: 0.6,
+            dead_zone_outer: 0.4,
+            ..KeyMapping::default()
+        };
+        let validation = validate_mapping(&mapping);
+
+        assert!(validation.has_errors());
+        assert_eq!(codes(&validation), vec![ValidationCode::InvertedDeadZones]);
+    }
+
+    #[test]
+    fn extreme_strength_is_limited() {
+        let mapping = KeyMapping {
+            response_curve: ResponseCurve::Exponential,
+            curve_params: CurveParams {
+                strength: 100.0,
+                ..CurveParams::default()
+            },
+            ..KeyMapping::default()
+        };
+        let validation = validate_mapping(&mapping);
+
+        assert!(!validation.has_errors());
+        assert_eq!(codes(&validation), vec![ValidationCode::InvalidStrength]);
+    }
+
+    #[test]
+    fn custom_point_diagnostics() {
+        let validation = validate_mapping(&custom_mapping(vec![
+            (0.0, 0.0),
+            (0.8, 0.4),
+            (0.5, 0.6),
+            (0.5, 0.7),
+            (1.0, 1.2),
+        ]));
+        let found = codes(&validation);
+
+        assert!(found.contains(&ValidationCode::PointOutOfRange));
+        assert!(found.contains(&ValidationCode::UnsortedPoints));
+        assert!(found.contains(&ValidationCode::DuplicatePointX));
+        assert!(found.contains(&ValidationCode::NonMonotonicPoints));
+        assert!(validation.has_errors());
+    }
+
+    #[test]
+    fn monotonic_sorted_points_are_clean() {
+        let validation =
+            validate_mapping(&custom_mapping(vec![(0.0, 0.0), (0.5, 0.3), (1.0, 1.0)]));
+        assert!(validation.issues.is_empty());
+    }
+}